@@ -26,6 +26,113 @@ macro_rules! setter {
     };
 }
 
+macro_rules! deadline {
+    () => {
+        /// Fails with [`Error::Timeout`](crate::Error::Timeout) when the call does not complete within `x`.
+        /// Unlike `timeout`, this is measured on the client and also applies when the driver hangs.
+        pub fn deadline(mut self, x: Duration) -> Self {
+            self.deadline = Some(x);
+            self
+        }
+
+        pub fn clear_deadline(mut self) -> Self {
+            self.deadline = None;
+            self
+        }
+    };
+}
+
+/// `mask`, `animations` and `caret` setters for screenshot builders with a `capture` field
+macro_rules! capture {
+    () => {
+        /// Elements painted over with `mask_color` in the screenshot. Element handles and selectors
+        /// must belong to the frame that is captured, a page's main frame for a page. Element
+        /// handles of other frames fail with [`Error::MaskAcrossFrames`](crate::Error::MaskAcrossFrames).
+        pub fn mask(mut self, x: Vec<crate::api::Mask>) -> Self {
+            self.capture.mask = Some(x);
+            self
+        }
+
+        pub fn clear_mask(mut self) -> Self {
+            self.capture.mask = None;
+            self
+        }
+
+        /// Adds a selector or an element to `mask`
+        pub fn add_mask<M: Into<crate::api::Mask>>(mut self, x: M) -> Self {
+            self.capture
+                .mask
+                .get_or_insert_with(Vec::new)
+                .push(x.into());
+            self
+        }
+
+        /// CSS color of the masks, defaults to `#FF00FF`.
+        pub fn mask_color(mut self, x: &str) -> Self {
+            self.capture.mask_color = Some(x.into());
+            self
+        }
+
+        pub fn clear_mask_color(mut self) -> Self {
+            self.capture.mask_color = None;
+            self
+        }
+
+        /// With `Disabled`, CSS animations and transitions are stopped before capturing. Defaults to
+        /// `Allow`.
+        pub fn animations(mut self, x: crate::api::ScreenshotAnimations) -> Self {
+            self.capture.animations = Some(x);
+            self
+        }
+
+        pub fn clear_animations(mut self) -> Self {
+            self.capture.animations = None;
+            self
+        }
+
+        /// With `Hide`, text carets are made transparent while capturing. Defaults to `Initial`.
+        pub fn caret(mut self, x: crate::api::ScreenshotCaret) -> Self {
+            self.capture.caret = Some(x);
+            self
+        }
+
+        pub fn clear_caret(mut self) -> Self {
+            self.capture.caret = None;
+            self
+        }
+    };
+}
+
+/// `get_by_*` selector builders for a type with `fn scope(&self) -> Scope`
+macro_rules! get_by {
+    () => {
+        /// Finds elements by ARIA role. Filter further by accessible name and state.
+        pub fn get_by_role<'a>(&self, role: AriaRole) -> GetByRole<'a> {
+            GetByRole::new(self.scope(), role)
+        }
+
+        /// Finds the smallest elements containing the text, or matching the regular expression.
+        pub fn get_by_text<T: Into<TextMatch>>(&self, text: T) -> GetBy {
+            GetBy::text(self.scope(), text.into())
+        }
+
+        /// Finds form controls by the text of their `<label>` or by `aria-label`.
+        pub fn get_by_label(&self, text: &str) -> GetBy { GetBy::label(self.scope(), text) }
+
+        pub fn get_by_placeholder(&self, text: &str) -> GetBy {
+            GetBy::placeholder(self.scope(), text)
+        }
+
+        pub fn get_by_alt_text(&self, text: &str) -> GetBy { GetBy::alt_text(self.scope(), text) }
+
+        pub fn get_by_title(&self, text: &str) -> GetBy { GetBy::title(self.scope(), text) }
+
+        /// Finds elements by the test id attribute.
+        /// See [`Selectors::set_test_id_attribute`](crate::api::Selectors::set_test_id_attribute).
+        pub fn get_by_test_id(&self, id: &str) -> GetBy { GetBy::test_id(self.scope(), id) }
+    };
+}
+
 #[doc(hidden)]
 #[macro_export]
 macro_rules! subscribe_event {
@@ -54,37 +161,56 @@ pub mod accessibility;
 pub mod browser;
 pub mod browser_context;
 pub mod browser_type;
+mod capture;
 pub mod console_message;
 pub mod dialog;
 pub mod download;
+pub mod drag;
 pub mod element_handle;
+pub mod expect;
+pub mod extract;
 pub mod file_chooser;
+pub mod form;
 pub mod frame;
+mod generate_selector;
+pub mod get_by;
 pub mod js_handle;
+pub mod key;
+pub mod locator;
 pub mod page;
 pub mod request;
 pub mod response;
 pub mod route;
 pub mod selectors;
+pub mod table;
 pub mod video;
+#[cfg(feature = "visual")]
+pub mod visual;
 pub mod websocket;
 pub mod worker;
 
-pub use crate::imp::{core::DateTime, utils::*};
+pub use crate::imp::{
+    core::{Bytes, DateTime, JsMap, JsSet, JsValue},
+    utils::*
+};
 
 pub use self::playwright::Playwright;
 pub use accessibility::Accessibility;
 pub use browser::Browser;
 pub use browser_context::BrowserContext;
 pub use browser_type::BrowserType;
+pub use capture::Mask;
 pub use console_message::ConsoleMessage;
 pub use dialog::Dialog;
 pub use download::Download;
 pub use element_handle::ElementHandle;
 pub use file_chooser::FileChooser;
-pub use frame::Frame;
+pub use frame::{Frame, FrameTree};
+pub use get_by::{AriaRole, TextMatch};
 pub use input_device::{Keyboard, Mouse, TouchScreen};
 pub use js_handle::JsHandle;
+pub use key::{Key, KeyInput, Shortcut};
+pub use locator::{FrameLocator, Locator};
 pub use page::Page;
 pub use request::Request;
 pub use response::Response;
@@ -68,18 +68,27 @@ impl Accessibility {
 
 pub struct SnapshotBuilder {
     inner: Weak<PageImpl>,
-    args: SnapshotArgs
+    args: SnapshotArgs,
+    deadline: Option<Duration>
 }
 
 impl SnapshotBuilder {
     fn new(inner: Weak<PageImpl>) -> Self {
         let args = SnapshotArgs::default();
-        Self { inner, args }
+        Self {
+            inner,
+            args,
+            deadline: None
+        }
     }
 
     pub async fn snapshot(self) -> ArcResult<Option<SnapshotResponse>> {
-        let Self { inner, args } = self;
-        upgrade(&inner)?.accessibility_snapshot(args).await
+        let Self {
+            inner,
+            args,
+            deadline
+        } = self;
+        with_deadline(deadline, upgrade(&inner)?.accessibility_snapshot(args)).await
     }
 
     /// The root DOM element for the snapshot. Defaults to the whole page.
@@ -89,6 +98,8 @@ impl SnapshotBuilder {
         Ok(self)
     }
 
+    deadline! {}
+
     setter!(
         /// Prune uninteresting nodes from the tree. Defaults to `true`.
         interesting_only: Option<bool>
@@ -81,20 +81,26 @@ impl Browser {
 /// [`Browser::context_builder`]
 pub struct ContextBuilder<'e, 'f, 'g, 'h, 'i, 'j, 'k> {
     inner: Weak<imp::browser::Browser>,
-    args: NewContextArgs<'e, 'f, 'g, 'h, 'i, 'j, 'k>
+    args: NewContextArgs<'e, 'f, 'g, 'h, 'i, 'j, 'k>,
+    deadline: Option<Duration>
 }
 
 impl<'e, 'f, 'g, 'h, 'i, 'j, 'k> ContextBuilder<'e, 'f, 'g, 'h, 'i, 'j, 'k> {
     pub async fn build(self) -> Result<BrowserContext, Arc<Error>> {
-        let Self { inner, args } = self;
-        let r = upgrade(&inner)?.new_context(args).await?;
+        let Self {
+            inner,
+            args,
+            deadline
+        } = self;
+        let r = with_deadline(deadline, upgrade(&inner)?.new_context(args)).await?;
         Ok(BrowserContext::new(r))
     }
 
     fn new(inner: Weak<imp::browser::Browser>) -> Self {
         Self {
             inner,
-            args: NewContextArgs::default()
+            args: NewContextArgs::default(),
+            deadline: None
         }
     }
 
@@ -102,6 +108,8 @@ impl<'e, 'f, 'g, 'h, 'i, 'j, 'k> ContextBuilder<'e, 'f, 'g, 'h, 'i, 'j, 'k> {
         DeviceDescriptor::set_context(device, self)
     }
 
+    deadline! {}
+
     setter! {
         /// Whether to automatically download all the attachments. Defaults to `false` where all the downloads are canceled.
         accept_downloads: Option<bool>,
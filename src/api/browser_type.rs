@@ -95,23 +95,31 @@ impl BrowserType {
 /// [`BrowserType::launcher`]
 pub struct Launcher<'a, 'b, 'c> {
     inner: Weak<Impl>,
-    args: LaunchArgs<'a, 'b, 'c>
+    args: LaunchArgs<'a, 'b, 'c>,
+    deadline: Option<Duration>
 }
 
 impl<'a, 'b, 'c> Launcher<'a, 'b, 'c> {
     pub async fn launch(self) -> Result<Browser, Arc<Error>> {
-        let Self { inner, args } = self;
-        let r = upgrade(&inner)?.launch(args).await?;
+        let Self {
+            inner,
+            args,
+            deadline
+        } = self;
+        let r = with_deadline(deadline, upgrade(&inner)?.launch(args)).await?;
         Ok(Browser::new(r))
     }
 
     fn new(inner: Weak<Impl>) -> Self {
         Launcher {
             inner,
-            args: LaunchArgs::default()
+            args: LaunchArgs::default(),
+            deadline: None
         }
     }
 
+    deadline! {}
+
     setter! {
         /// Path to a browser executable to run instead of the bundled one. If `executablePath` is a relative path, then it is
         /// resolved relative to the current working directory. Note that Playwright only works with the bundled Chromium, Firefox
@@ -167,22 +175,28 @@ impl<'a, 'b, 'c> Launcher<'a, 'b, 'c> {
 /// Has launch args and context args
 pub struct PersistentContextLauncher<'a, 'b, 'c, 'd, 'e, 'f, 'g, 'h, 'i, 'j, 'k> {
     inner: Weak<Impl>,
-    args: LaunchPersistentContextArgs<'a, 'b, 'c, 'd, 'e, 'f, 'g, 'h, 'i, 'j, 'k>
+    args: LaunchPersistentContextArgs<'a, 'b, 'c, 'd, 'e, 'f, 'g, 'h, 'i, 'j, 'k>,
+    deadline: Option<Duration>
 }
 
 impl<'a, 'b, 'c, 'd, 'e, 'f, 'g, 'h, 'i, 'j, 'k>
     PersistentContextLauncher<'a, 'b, 'c, 'd, 'e, 'f, 'g, 'h, 'i, 'j, 'k>
 {
     pub async fn launch(self) -> Result<BrowserContext, Arc<Error>> {
-        let Self { inner, args } = self;
-        let r = upgrade(&inner)?.launch_persistent_context(args).await?;
+        let Self {
+            inner,
+            args,
+            deadline
+        } = self;
+        let r = with_deadline(deadline, upgrade(&inner)?.launch_persistent_context(args)).await?;
         Ok(BrowserContext::new(r))
     }
 
     fn new(inner: Weak<Impl>, user_data_dir: &'a Path) -> Self {
         Self {
             inner,
-            args: LaunchPersistentContextArgs::new(user_data_dir)
+            args: LaunchPersistentContextArgs::new(user_data_dir),
+            deadline: None
         }
     }
 
@@ -190,6 +204,8 @@ impl<'a, 'b, 'c, 'd, 'e, 'f, 'g, 'h, 'i, 'j, 'k>
         DeviceDescriptor::set_persistent_context(device, self)
     }
 
+    deadline! {}
+
     setter! {
         /// Path to a browser executable to run instead of the bundled one. If `executablePath` is a relative path, then it is
         /// resolved relative to the current working directory. **BEWARE**: Playwright is only guaranteed to work with the bundled
@@ -300,23 +316,31 @@ impl<'a, 'b, 'c, 'd, 'e, 'f, 'g, 'h, 'i, 'j, 'k>
 
 pub struct ConnectOverCdpBuilder<'a> {
     inner: Weak<Impl>,
-    args: ConnectOverCdpArgs<'a>
+    args: ConnectOverCdpArgs<'a>,
+    deadline: Option<Duration>
 }
 
 impl<'a> ConnectOverCdpBuilder<'a> {
     pub async fn connect_over_cdp(self) -> ArcResult<Browser> {
-        let Self { inner, args } = self;
-        let r = upgrade(&inner)?.connect_over_cdp(args).await?;
+        let Self {
+            inner,
+            args,
+            deadline
+        } = self;
+        let r = with_deadline(deadline, upgrade(&inner)?.connect_over_cdp(args)).await?;
         Ok(Browser::new(r))
     }
 
     fn new(inner: Weak<Impl>, endpoint_url: &'a str) -> Self {
         Self {
             inner,
-            args: ConnectOverCdpArgs::new(endpoint_url)
+            args: ConnectOverCdpArgs::new(endpoint_url),
+            deadline: None
         }
     }
 
+    deadline! {}
+
     setter! {
         /// Additional HTTP headers to be sent with web socket connect request. Optional.
         headers: Option<HashMap<String, String>>,
@@ -0,0 +1,164 @@
+//! Preparing a page for a stable screenshot and undoing it afterwards.
+use crate::{
+    api::{ElementHandle, Frame},
+    imp::{
+        core::*,
+        prelude::*,
+        utils::{ScreenshotAnimations, ScreenshotCaret}
+    }
+};
+
+/// What a screenshot's `mask` paints over: the elements a selector matches, or an element
+#[derive(Debug)]
+pub enum Mask {
+    Selector(String),
+    Element(ElementHandle)
+}
+
+impl From<&str> for Mask {
+    fn from(s: &str) -> Self { Self::Selector(s.into()) }
+}
+
+impl From<String> for Mask {
+    fn from(s: String) -> Self { Self::Selector(s) }
+}
+
+impl From<ElementHandle> for Mask {
+    fn from(e: ElementHandle) -> Self { Self::Element(e) }
+}
+
+impl From<&ElementHandle> for Mask {
+    fn from(e: &ElementHandle) -> Self { Self::Element(e.share()) }
+}
+
+/// Options of screenshot builders applied around the capture
+#[derive(Debug, Default)]
+pub(crate) struct CaptureArgs {
+    pub(crate) mask: Option<Vec<Mask>>,
+    pub(crate) mask_color: Option<String>,
+    pub(crate) animations: Option<ScreenshotAnimations>,
+    pub(crate) caret: Option<ScreenshotCaret>
+}
+
+impl CaptureArgs {
+    pub(crate) fn is_empty(&self) -> bool {
+        self.mask.as_ref().map(Vec::is_empty).unwrap_or(true)
+            && self.animations != Some(ScreenshotAnimations::Disabled)
+            && self.caret != Some(ScreenshotCaret::Hide)
+    }
+
+    /// Resolves the mask selectors in `frame`. Masks are only painted in `frame`, so elements of
+    /// other frames are rejected.
+    pub(crate) async fn resolve(self, frame: &Frame) -> ArcResult<Capture> {
+        let mut mask = Vec::new();
+        for m in self.mask.into_iter().flatten() {
+            match m {
+                // Only handles created here are disposed, after the capture
+                Mask::Selector(s) => mask.extend(
+                    frame
+                        .query_selector_all(&s)
+                        .await?
+                        .into_iter()
+                        .map(|e| e.dispose_on_drop(true))
+                ),
+                Mask::Element(e) => {
+                    if e.owner_frame().await?.as_ref() != Some(frame) {
+                        return Err(Error::MaskAcrossFrames.into());
+                    }
+                    mask.push(e)
+                }
+            }
+        }
+        Ok(Capture {
+            mask,
+            mask_color: self.mask_color,
+            disable_animations: self.animations == Some(ScreenshotAnimations::Disabled),
+            hide_caret: self.caret == Some(ScreenshotCaret::Hide)
+        })
+    }
+}
+
+/// Changes made in a frame before capturing it
+pub(crate) struct Capture {
+    /// Painted over with `mask_color`
+    pub(crate) mask: Vec<ElementHandle>,
+    /// CSS color, defaults to `#FF00FF`
+    pub(crate) mask_color: Option<String>,
+    pub(crate) disable_animations: bool,
+    pub(crate) hide_caret: bool
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Args<'a> {
+    mask: &'a [ElementHandle],
+    mask_color: &'a str,
+    disable_animations: bool,
+    hide_caret: bool
+}
+
+impl Capture {
+    fn is_empty(&self) -> bool {
+        self.mask.is_empty() && !self.disable_animations && !self.hide_caret
+    }
+
+    /// Runs `capture` with the changes applied in `frame`, and removes them even if it fails
+    pub(crate) async fn around<F, T>(&self, frame: &Frame, capture: F) -> ArcResult<T>
+    where
+        F: Future<Output = ArcResult<T>>
+    {
+        if self.is_empty() {
+            return capture.await;
+        }
+        let args = Args {
+            mask: &self.mask,
+            mask_color: self.mask_color.as_deref().unwrap_or("#FF00FF"),
+            disable_animations: self.disable_animations,
+            hide_caret: self.hide_caret
+        };
+        let _: Value = frame.evaluate(PREPARE, args).await?;
+        let result = capture.await;
+        let _: Value = frame.evaluate(RESTORE, ()).await?;
+        result
+    }
+}
+
+/// Finite animations are finished and infinite ones cancelled to their initial state.
+/// Masks are boxes over the elements, so they are also painted over what covers the elements.
+const PREPARE: &str = r#"({ mask, maskColor, disableAnimations, hideCaret }) => {
+    const marker = '__playwright_capture__';
+    if (disableAnimations) {
+        for (const a of document.getAnimations()) {
+            const end = a.effect ? a.effect.getComputedTiming().endTime : Infinity;
+            if (Number.isFinite(end)) a.finish();
+            else a.cancel();
+        }
+    }
+    if (hideCaret) {
+        const style = document.createElement('style');
+        style.className = marker;
+        style.textContent = '* { caret-color: transparent !important; }';
+        (document.head || document.documentElement).appendChild(style);
+    }
+    for (const e of mask) {
+        const r = e.getBoundingClientRect();
+        const box = document.createElement('div');
+        box.className = marker;
+        Object.assign(box.style, {
+            position: 'absolute',
+            left: `${r.left + window.scrollX}px`,
+            top: `${r.top + window.scrollY}px`,
+            width: `${r.width}px`,
+            height: `${r.height}px`,
+            background: maskColor,
+            zIndex: '2147483647',
+            pointerEvents: 'none'
+        });
+        document.documentElement.appendChild(box);
+    }
+}"#;
+
+const RESTORE: &str = r#"() => {
+    for (const e of document.querySelectorAll('.__playwright_capture__'))
+        e.remove();
+}"#;
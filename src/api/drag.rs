@@ -0,0 +1,285 @@
+//! Dragging elements onto each other, and files onto drop zones.
+//!
+//! A source inside an element with the `draggable` property, like `[draggable=true]`, images and
+//! links, gets HTML5 drag events that share a `DataTransfer`. Other sources are dragged with the
+//! mouse, for libraries that follow pointer events.
+//!
+//! ```ignore
+//! page.drag_and_drop_builder("#todo .card", "#done")
+//!     .target_position((10.0, 10.0).into())
+//!     .drag_and_drop()
+//!     .await?;
+//! ```
+use crate::{
+    api::{frame::FrameState, ElementHandle, Frame},
+    imp::{
+        core::*,
+        prelude::*,
+        utils::{ElementState, File, Position, ScrollAlignment}
+    }
+};
+use std::time::Instant;
+
+enum Target<'a> {
+    Selectors(Frame, &'a str, &'a str),
+    Elements(&'a ElementHandle, &'a ElementHandle)
+}
+
+pub struct DragAndDropBuilder<'a> {
+    target: Target<'a>,
+    args: DragAndDropArgs,
+    deadline: Option<Duration>
+}
+
+#[derive(Debug, Default)]
+struct DragAndDropArgs {
+    source_position: Option<Position>,
+    target_position: Option<Position>,
+    force: Option<bool>,
+    timeout: Option<f64>,
+    steps: Option<i32>
+}
+
+impl<'a> DragAndDropBuilder<'a> {
+    pub(crate) fn new(frame: Frame, source: &'a str, target: &'a str) -> Self {
+        Self {
+            target: Target::Selectors(frame, source, target),
+            args: DragAndDropArgs::default(),
+            deadline: None
+        }
+    }
+
+    pub(crate) fn elements(source: &'a ElementHandle, target: &'a ElementHandle) -> Self {
+        Self {
+            target: Target::Elements(source, target),
+            args: DragAndDropArgs::default(),
+            deadline: None
+        }
+    }
+
+    pub async fn drag_and_drop(self) -> ArcResult<()> {
+        let Self {
+            target,
+            args,
+            deadline
+        } = self;
+        let f = async {
+            let frame = match &target {
+                Target::Selectors(frame, ..) => frame.clone(),
+                Target::Elements(source, _) => {
+                    source.owner_frame().await?.ok_or(Error::ObjectNotFound)?
+                }
+            };
+            let budget = Budget::new(&frame, args.timeout)?;
+            match target {
+                Target::Selectors(frame, source, target) => {
+                    let source = resolve(&frame, source, &args, &budget).await?;
+                    let target = resolve(&frame, target, &args, &budget).await?;
+                    drag(&source, &target, &args, &budget).await
+                }
+                Target::Elements(source, target) => {
+                    if args.force != Some(true) {
+                        for e in &[source, target] {
+                            e.wait_for_element_state(ElementState::Visible, Some(budget.left()?))
+                                .await?;
+                        }
+                    }
+                    drag(source, target, &args, &budget).await
+                }
+            }
+        };
+        with_deadline(deadline, f).await
+    }
+
+    deadline! {}
+
+    setter! {
+        /// A point relative to the top-left corner of the source's padding box to start dragging
+        /// from. Defaults to the center.
+        source_position: Option<Position>,
+        /// A point relative to the top-left corner of the target's padding box to drop at.
+        /// Defaults to the center.
+        target_position: Option<Position>,
+        /// Whether to bypass waiting for the elements to be visible. Defaults to `false`.
+        force: Option<bool>,
+        /// Maximum time in milliseconds for all the waits together, defaults to `30000`.
+        /// Pass `0` to disable the timeout. The default can be changed with
+        /// `Page::set_default_timeout`.
+        timeout: Option<f64>,
+        /// Number of `mousemove` events on the way to the target when dragging with the mouse.
+        /// Defaults to 5.
+        steps: Option<i32>
+    }
+}
+
+/// Timeout shared by the waits of one drag, so that they add up to at most `timeout`
+struct Budget {
+    timeout: f64,
+    started: Instant
+}
+
+impl Budget {
+    fn new(frame: &Frame, timeout: Option<f64>) -> ArcResult<Self> {
+        let timeout = match (timeout, frame.page()?) {
+            (Some(t), _) => t,
+            (None, Some(p)) => p.default_timeout()? as f64,
+            (None, None) => 30000.
+        };
+        Ok(Self {
+            timeout,
+            started: Instant::now()
+        })
+    }
+
+    /// Time left in milliseconds. 0 disables the timeout.
+    fn left(&self) -> ArcResult<f64> {
+        if self.timeout <= 0. {
+            return Ok(0.);
+        }
+        let left = self.timeout - self.started.elapsed().as_millis() as f64;
+        if left > 0. {
+            Ok(left)
+        } else {
+            Err(Arc::new(Error::Timeout))
+        }
+    }
+}
+
+async fn resolve(
+    frame: &Frame,
+    selector: &str,
+    args: &DragAndDropArgs,
+    budget: &Budget
+) -> ArcResult<ElementHandle> {
+    let state = if args.force == Some(true) {
+        FrameState::Attached
+    } else {
+        FrameState::Visible
+    };
+    Ok(frame
+        .wait_for_selector_builder(selector)
+        .state(state)
+        .timeout(budget.left()?)
+        .wait_for_selector()
+        .await?
+        .ok_or(Error::ObjectNotFound)?)
+}
+
+async fn drag(
+    source: &ElementHandle,
+    target: &ElementHandle,
+    args: &DragAndDropArgs,
+    budget: &Budget
+) -> ArcResult<()> {
+    let frame = source.owner_frame().await?.ok_or(Error::ObjectNotFound)?;
+    if target.owner_frame().await?.as_ref() != Some(&frame) {
+        return Err(Error::DragAcrossFrames.into());
+    }
+    let force = args.force == Some(true);
+    let draggable: bool = frame.evaluate(DRAGGABLE, source).await?;
+    if draggable {
+        // Only scrolls and waits here, the events compute their own coordinates
+        point(source, args.source_position, force, Some(budget.left()?)).await?;
+        point(target, args.target_position, force, Some(budget.left()?)).await?;
+        let files: &[File] = &[];
+        let arg = (
+            source,
+            target,
+            args.source_position,
+            args.target_position,
+            files
+        );
+        let _: bool = frame.evaluate(DRAG, arg).await?;
+        return Ok(());
+    }
+    let page = frame.page()?.ok_or(Error::ObjectNotFound)?;
+    let from = point(source, args.source_position, force, Some(budget.left()?)).await?;
+    page.mouse.r#move(from.x, from.y, None).await?;
+    page.mouse.down(None, None).await?;
+    let to = point(target, args.target_position, force, Some(budget.left()?)).await?;
+    let steps = args.steps.unwrap_or(5);
+    page.mouse.r#move(to.x, to.y, Some(steps)).await?;
+    page.mouse.up(None, None).await
+}
+
+/// Dispatches `dragenter`, `dragover` and, when accepted, `drop` with the files on `target`
+pub(crate) async fn drop_files(target: &ElementHandle, files: &[File]) -> ArcResult<bool> {
+    let frame = target.owner_frame().await?.ok_or(Error::ObjectNotFound)?;
+    let arg = (
+        None::<&ElementHandle>,
+        target,
+        None::<Position>,
+        None::<Position>,
+        files
+    );
+    frame.evaluate(DRAG, arg).await
+}
+
+/// Scrolls the element into view and returns the point in the viewport
+async fn point(
+    e: &ElementHandle,
+    position: Option<Position>,
+    force: bool,
+    timeout: Option<f64>
+) -> ArcResult<Position> {
+    if force {
+        e.scroll_into_view_builder()
+            .block(ScrollAlignment::Nearest)
+            .inline(ScrollAlignment::Nearest)
+            .scroll_into_view()
+            .await?;
+    } else {
+        e.scroll_into_view_if_needed(timeout).await?;
+    }
+    let b = e.bounding_box().await?.ok_or(Error::ObjectNotFound)?;
+    let p = match position {
+        Some(p) => Position {
+            x: b.x + p.x,
+            y: b.y + p.y
+        },
+        None => Position {
+            x: b.x + b.width / 2.0,
+            y: b.y + b.height / 2.0
+        }
+    };
+    Ok(p)
+}
+
+const DRAGGABLE: &str = r#"e => {
+    for (let n = e; n; n = n.parentElement)
+        if (n.draggable) return true;
+    return false;
+}"#;
+
+/// Takes `[source, target, sourcePosition, targetPosition, files]` and returns whether the target
+/// accepted the drop. Without a source only the target's events are dispatched.
+const DRAG: &str = r#"([source, target, sourcePosition, targetPosition, files]) => {
+    const dataTransfer = new DataTransfer();
+    for (const f of files) {
+        const bytes = Uint8Array.from(atob(f.buffer), c => c.charCodeAt(0));
+        dataTransfer.items.add(new File([bytes], f.name, { type: f.mime }));
+    }
+    const at = (e, p) => {
+        const r = e.getBoundingClientRect();
+        return p ? [r.left + p.x, r.top + p.y] : [r.left + r.width / 2, r.top + r.height / 2];
+    };
+    const fire = (e, type, [clientX, clientY]) => e.dispatchEvent(new DragEvent(type, {
+        bubbles: true, cancelable: true, composed: true, clientX, clientY, dataTransfer
+    }));
+    let dragged = null;
+    if (source) {
+        dragged = source;
+        while (!dragged.draggable) dragged = dragged.parentElement;
+        const start = at(source, sourcePosition);
+        if (!fire(dragged, 'dragstart', start))
+            return false;
+        fire(dragged, 'drag', start);
+    }
+    const point = at(target, targetPosition);
+    fire(target, 'dragenter', point);
+    const accepted = !fire(target, 'dragover', point);
+    fire(target, accepted ? 'drop' : 'dragleave', point);
+    if (dragged)
+        fire(dragged, 'dragend', point);
+    return accepted;
+}"#;
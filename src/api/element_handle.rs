@@ -1,16 +1,25 @@
 use crate::{
-    api::Frame,
+    api::{
+        capture::CaptureArgs,
+        drag::{self, DragAndDropBuilder},
+        extract::{Extract, Field, EXTRACT},
+        generate_selector,
+        get_by::{AriaRole, GetBy, GetByRole, TextMatch},
+        locator::Scope,
+        table::TableBuilder,
+        Frame, KeyInput, Locator
+    },
     imp::{
         core::*,
         element_handle::{
             CheckArgs, ClickArgs, ElementHandle as Impl, FillArgs, HoverArgs, Opt, PressArgs,
-            ScreenshotArgs, SelectOptionArgs, SetInputFilesArgs, TapArgs, TypeArgs,
-            WaitForSelectorArgs
+            ScreenshotArgs, ScrollIntoViewArgs, SelectOptionArgs, SetInputFilesArgs, TapArgs,
+            TypeArgs, WaitForSelectorArgs
         },
         prelude::*,
         utils::{
             ElementState, File, FloatRect, KeyboardModifier, MouseButton, Position, ScreenshotType,
-            WaitForSelectorState
+            ScrollAlignment, ScrollBehavior, WaitForSelectorState
         }
     }
 };
@@ -38,7 +47,21 @@ use crate::{
 /// methods.
 #[derive(Debug)]
 pub struct ElementHandle {
-    inner: Weak<Impl>
+    inner: Weak<Impl>,
+    dispose_on_drop: bool
+}
+
+impl Drop for ElementHandle {
+    fn drop(&mut self) {
+        if !self.dispose_on_drop {
+            return;
+        }
+        if let Some(inner) = self.inner.upgrade() {
+            spawn_detached(async move {
+                inner.dispose().await.ok();
+            });
+        }
+    }
 }
 
 impl PartialEq for ElementHandle {
@@ -58,7 +81,22 @@ macro_rules! is_checked {
 }
 
 impl ElementHandle {
-    pub(crate) fn new(inner: Weak<Impl>) -> Self { Self { inner } }
+    pub(crate) fn new(inner: Weak<Impl>) -> Self {
+        Self {
+            inner,
+            dispose_on_drop: false
+        }
+    }
+
+    /// Sends `dispose` in the background when this value is dropped.
+    /// Other `ElementHandle` values referring to the same element become unusable after that.
+    pub fn dispose_on_drop(mut self, x: bool) -> Self {
+        self.dispose_on_drop = x;
+        self
+    }
+
+    /// Stops referencing the element. The element is no longer available for evaluation.
+    pub async fn dispose(&self) -> ArcResult<()> { upgrade(&self.inner)?.dispose().await }
 
     pub(crate) fn guid(&self) -> Result<Str<Guid>, Error> {
         Ok(upgrade(&self.inner)?.guid().to_owned())
@@ -73,6 +111,18 @@ impl ElementHandle {
             .map(ElementHandle::new))
     }
 
+    /// Returns a locator that resolves `selector` in this element's subtree on every action.
+    pub fn locator(&self, selector: &str) -> Locator {
+        Locator::in_element(self.inner.clone(), selector)
+    }
+
+    pub(crate) fn scope(&self) -> Scope { Scope::Element(self.inner.clone()) }
+
+    /// Another handle to the same element that is not disposed on drop
+    pub(crate) fn share(&self) -> Self { Self::new(self.inner.clone()) }
+
+    get_by! {}
+
     /// The method finds all elements matching the specified selector in the `ElementHandle`s subtree.
     /// If no elements match the selector, returns empty array.
     pub async fn query_selector_all(&self, selector: &str) -> ArcResult<Vec<ElementHandle>> {
@@ -98,6 +148,64 @@ impl ElementHandle {
         Ok(upgrade(&self.inner)?.owner_frame().await?.map(Frame::new))
     }
 
+    /// Extracts `T` from this element's subtree in one round-trip. See [`extract`](crate::api::extract).
+    pub async fn extract<T: Extract>(&self) -> ArcResult<T> {
+        self.extract_with(&T::schema()).await
+    }
+
+    /// Extracts data described by `schema` from this element's subtree in one round-trip.
+    pub async fn extract_with<U>(&self, schema: &Field) -> ArcResult<U>
+    where
+        U: DeserializeOwned
+    {
+        let frame = self.owner_frame().await?.ok_or(Error::ObjectNotFound)?;
+        frame.evaluate(EXTRACT, (Some(self), schema)).await
+    }
+
+    /// Rows of this table, or of the first table inside it, keyed by header.
+    /// See [`table`](crate::api::table).
+    pub async fn table(&self) -> ArcResult<Vec<HashMap<String, String>>> {
+        self.table_builder().rows().await
+    }
+
+    pub fn table_builder(&self) -> TableBuilder<'_> { TableBuilder::element(self) }
+
+    /// Drags this element onto `target`. See [`drag`](crate::api::drag).
+    /// Fails with [`Error::DragAcrossFrames`](crate::Error::DragAcrossFrames) when `target` is in
+    /// another frame.
+    pub async fn drag_to(&self, target: &ElementHandle) -> ArcResult<()> {
+        self.drag_to_builder(target).drag_and_drop().await
+    }
+
+    pub fn drag_to_builder<'a>(&'a self, target: &'a ElementHandle) -> DragAndDropBuilder<'a> {
+        DragAndDropBuilder::elements(self, target)
+    }
+
+    /// Drops the files onto this element as if they were dragged from outside the browser.
+    /// Returns whether the element accepted the drop by cancelling `dragover`.
+    pub async fn drop_files(&self, files: &[File]) -> ArcResult<bool> {
+        drag::drop_files(self, files).await
+    }
+
+    /// Returns a selector that matches only this element in its [`owner_frame`](ElementHandle::owner_frame).
+    /// Test ids are preferred, then role with accessible name, id, text, short css and finally a css path.
+    pub async fn generate_selector(&self) -> ArcResult<Option<String>> {
+        Ok(self.generate_selectors().await?.into_iter().next())
+    }
+
+    /// All selectors that match only this element in its owner frame, best first.
+    pub async fn generate_selectors(&self) -> ArcResult<Vec<String>> {
+        match self.owner_frame().await? {
+            Some(f) => generate_selector::generate(self, &f).await,
+            None => Ok(Vec::new())
+        }
+    }
+
+    /// Selectors from the main frame down to this element: one for each `iframe` element on the way, then this element.
+    pub async fn generate_selector_chain(&self) -> ArcResult<Option<Vec<String>>> {
+        generate_selector::chain(self).await
+    }
+
     /// Returns the content frame for element handles referencing iframe nodes, or `null` otherwise
     pub async fn content_frame(&self) -> ArcResult<Option<Frame>> {
         Ok(upgrade(&self.inner)?.content_frame().await?.map(Frame::new))
@@ -219,7 +327,7 @@ impl ElementHandle {
     /// await elementHandle.press('Enter');
     /// ```
     pub fn type_builder<'a>(&self, text: &'a str) -> TypeBuilder<'a> {
-        TypeBuilder::new(self.inner.clone(), text)
+        TypeBuilder::new(self.inner.clone(), text.into())
     }
 
     /// Focuses the element, and then uses [`method: Keyboard.down`] and [`method: Keyboard.up`].
@@ -240,8 +348,8 @@ impl ElementHandle {
     ///
     /// Shortcuts such as `key: "Control+o"` or `key: "Control+Shift+T"` are supported as well. When specified with the
     /// modifier, modifier is pressed and being held while the subsequent key is being pressed.
-    pub fn press_builder<'a>(&self, key: &'a str) -> PressBuilder<'a> {
-        PressBuilder::new(self.inner.clone(), key)
+    pub fn press_builder(&self, key: impl Into<KeyInput>) -> PressBuilder<'static> {
+        PressBuilder::new(self.inner.clone(), key.into().into_string().into())
     }
 
     /// This method waits for actionability checks, then tries to scroll element into view, unless it is
@@ -256,6 +364,13 @@ impl ElementHandle {
             .await
     }
 
+    /// Scrolls the element into view like
+    /// [Element.scrollIntoView](https://developer.mozilla.org/en-US/docs/Web/API/Element/scrollIntoView),
+    /// with control over where it is aligned. Does not wait for actionability checks.
+    pub fn scroll_into_view_builder(&self) -> ScrollIntoViewBuilder {
+        ScrollIntoViewBuilder::new(self.inner.clone())
+    }
+
     /// This method waits for actionability checks, then focuses the element and selects all its text
     /// content.
     pub async fn select_text(&self, timeout: Option<f64>) -> ArcResult<()> {
@@ -291,6 +406,16 @@ impl ElementHandle {
         ScreenshotBuilder::new(self.inner.clone())
     }
 
+    /// Compares a screenshot of the element with the image at `baseline`. See
+    /// [`visual`](crate::api::visual).
+    #[cfg(feature = "visual")]
+    pub fn compare_screenshot_builder<P: Into<PathBuf>>(
+        &self,
+        baseline: P
+    ) -> crate::api::visual::CompareScreenshotBuilder<'_> {
+        crate::api::visual::CompareScreenshotBuilder::element(self, baseline.into())
+    }
+
     /// Returns when the element satisfies the `state`.
     pub async fn wait_for_element_state(
         &self,
@@ -406,20 +531,31 @@ impl ElementHandle {}
 
 pub struct HoverBuilder {
     inner: Weak<Impl>,
-    args: HoverArgs
+    args: HoverArgs,
+    deadline: Option<Duration>
 }
 
 impl HoverBuilder {
     pub(crate) fn new(inner: Weak<Impl>) -> Self {
         let args = HoverArgs::default();
-        Self { inner, args }
+        Self {
+            inner,
+            args,
+            deadline: None
+        }
     }
 
     pub async fn goto(self) -> Result<(), Arc<Error>> {
-        let Self { inner, args } = self;
-        upgrade(&inner)?.hover(args).await
+        let Self {
+            inner,
+            args,
+            deadline
+        } = self;
+        with_deadline(deadline, upgrade(&inner)?.hover(args)).await
     }
 
+    deadline! {}
+
     setter! {
         /// Whether to bypass the [actionability](https://playwright.dev/docs/actionability/) checks. Defaults to `false`.
         force: Option<bool>,
@@ -440,21 +576,32 @@ macro_rules! clicker {
     ($t: ident, $f: ident) => {
         pub struct $t {
             inner: Weak<Impl>,
-            args: ClickArgs
+            args: ClickArgs,
+            deadline: Option<Duration>
         }
 
         impl $t {
             pub(crate) fn new(inner: Weak<Impl>) -> Self {
                 let args = ClickArgs::default();
-                Self { inner, args }
+                Self {
+                    inner,
+                    args,
+                    deadline: None
+                }
             }
 
             pub async fn $f(self) -> Result<(), Arc<Error>> {
-                let Self { inner, args } = self;
-                let _ = upgrade(&inner)?.$f(args).await?;
+                let Self {
+                    inner,
+                    args,
+                    deadline
+                } = self;
+                let _ = with_deadline(deadline, upgrade(&inner)?.$f(args)).await?;
                 Ok(())
             }
 
+            deadline! {}
+
             setter! {
                 /// Defaults to `left`.
                 button: Option<MouseButton>,
@@ -488,21 +635,32 @@ macro_rules! check_builder {
     ($t: ident, $m: ident) => {
         pub struct $t {
             inner: Weak<Impl>,
-            args: CheckArgs
+            args: CheckArgs,
+            deadline: Option<Duration>
         }
 
         impl $t {
             pub(crate) fn new(inner: Weak<Impl>) -> Self {
                 let args = CheckArgs::default();
-                Self { inner, args }
+                Self {
+                    inner,
+                    args,
+                    deadline: None
+                }
             }
 
             pub async fn $m(self) -> Result<(), Arc<Error>> {
-                let Self { inner, args } = self;
-                let _ = upgrade(&inner)?.$m(args).await?;
+                let Self {
+                    inner,
+                    args,
+                    deadline
+                } = self;
+                let _ = with_deadline(deadline, upgrade(&inner)?.$m(args)).await?;
                 Ok(())
             }
 
+            deadline! {}
+
             setter! {
                 /// A point to use relative to the top-left corner of element padding box. If not specified, uses some visible point of the element.
                 position: Option<Position>,
@@ -526,21 +684,32 @@ check_builder!(UncheckBuilder, uncheck);
 
 pub struct TapBuilder {
     inner: Weak<Impl>,
-    args: TapArgs
+    args: TapArgs,
+    deadline: Option<Duration>
 }
 
 impl TapBuilder {
     pub(crate) fn new(inner: Weak<Impl>) -> Self {
         let args = TapArgs::default();
-        Self { inner, args }
+        Self {
+            inner,
+            args,
+            deadline: None
+        }
     }
 
     pub async fn tap(self) -> Result<(), Arc<Error>> {
-        let Self { inner, args } = self;
-        let _ = upgrade(&inner)?.tap(args).await?;
+        let Self {
+            inner,
+            args,
+            deadline
+        } = self;
+        let _ = with_deadline(deadline, upgrade(&inner)?.tap(args)).await?;
         Ok(())
     }
 
+    deadline! {}
+
     setter! {
         /// Whether to bypass the actionability checks. Defaults to `false`.
         force: Option<bool>,
@@ -561,23 +730,70 @@ impl TapBuilder {
     }
 }
 
+pub struct ScrollIntoViewBuilder {
+    inner: Weak<Impl>,
+    args: ScrollIntoViewArgs,
+    deadline: Option<Duration>
+}
+
+impl ScrollIntoViewBuilder {
+    pub(crate) fn new(inner: Weak<Impl>) -> Self {
+        Self {
+            inner,
+            args: ScrollIntoViewArgs::default(),
+            deadline: None
+        }
+    }
+
+    pub async fn scroll_into_view(self) -> ArcResult<()> {
+        let Self {
+            inner,
+            args,
+            deadline
+        } = self;
+        with_deadline(deadline, upgrade(&inner)?.scroll_into_view(args)).await
+    }
+
+    deadline! {}
+
+    setter! {
+        /// Vertical alignment. Defaults to `start`.
+        block: Option<ScrollAlignment>,
+        /// Horizontal alignment. Defaults to `nearest`.
+        inline: Option<ScrollAlignment>,
+        /// Defaults to `auto`. Smooth scrolling may still be in progress when this returns.
+        behavior: Option<ScrollBehavior>
+    }
+}
+
 pub struct FillBuilder<'a> {
     inner: Weak<Impl>,
-    args: FillArgs<'a>
+    args: FillArgs<'a>,
+    deadline: Option<Duration>
 }
 
 impl<'a> FillBuilder<'a> {
     pub(crate) fn new(inner: Weak<Impl>, value: &'a str) -> Self {
         let args = FillArgs::new(value);
-        Self { inner, args }
+        Self {
+            inner,
+            args,
+            deadline: None
+        }
     }
 
     pub async fn fill(self) -> Result<(), Arc<Error>> {
-        let Self { inner, args } = self;
-        let _ = upgrade(&inner)?.fill(args).await?;
+        let Self {
+            inner,
+            args,
+            deadline
+        } = self;
+        let _ = with_deadline(deadline, upgrade(&inner)?.fill(args)).await?;
         Ok(())
     }
 
+    deadline! {}
+
     setter! {
         /// Actions that initiate navigations are waiting for these navigations to happen and for pages to start loading. You can
         /// opt out of waiting via setting this flag. You would only need this option in the exceptional cases such as navigating to
@@ -591,21 +807,32 @@ macro_rules! type_builder {
     ($t: ident, $a: ident, $f: ident, $m: ident) => {
         pub struct $t<'a> {
             inner: Weak<Impl>,
-            args: $a<'a>
+            args: $a<'a>,
+            deadline: Option<Duration>
         }
 
         impl<'a> $t<'a> {
-            pub(crate) fn new(inner: Weak<Impl>, $f: &'a str) -> Self {
+            pub(crate) fn new(inner: Weak<Impl>, $f: Cow<'a, str>) -> Self {
                 let args = $a::new($f);
-                Self { inner, args }
+                Self {
+                    inner,
+                    args,
+                    deadline: None
+                }
             }
 
             pub async fn $m(self) -> Result<(), Arc<Error>> {
-                let Self { inner, args } = self;
-                let _ = upgrade(&inner)?.$m(args).await?;
+                let Self {
+                    inner,
+                    args,
+                    deadline
+                } = self;
+                let _ = with_deadline(deadline, upgrade(&inner)?.$m(args)).await?;
                 Ok(())
             }
 
+            deadline! {}
+
             setter! {
                 /// Time to wait between `keydown` and `keyup` in milliseconds. Defaults to 0.
                 delay: Option<f64>,
@@ -624,18 +851,40 @@ type_builder!(PressBuilder, PressArgs, key, press);
 
 pub struct ScreenshotBuilder<'a> {
     inner: Weak<Impl>,
-    args: ScreenshotArgs<'a>
+    args: ScreenshotArgs<'a>,
+    capture: CaptureArgs,
+    deadline: Option<Duration>
 }
 
 impl<'a> ScreenshotBuilder<'a> {
     pub(crate) fn new(inner: Weak<Impl>) -> Self {
         let args = ScreenshotArgs::default();
-        Self { inner, args }
+        Self {
+            inner,
+            args,
+            capture: CaptureArgs::default(),
+            deadline: None
+        }
     }
 
     pub async fn screenshot(self) -> ArcResult<Vec<u8>> {
-        let Self { inner, args } = self;
-        upgrade(&inner)?.screenshot(args).await
+        let Self {
+            inner,
+            args,
+            capture,
+            deadline
+        } = self;
+        let f = async move {
+            let e = upgrade(&inner)?;
+            if capture.is_empty() {
+                return e.screenshot(args).await;
+            }
+            let frame = e.owner_frame().await?.ok_or(Error::ObjectNotFound)?;
+            let frame = Frame::new(frame);
+            let capture = capture.resolve(&frame).await?;
+            capture.around(&frame, e.screenshot(args)).await
+        };
+        with_deadline(deadline, f).await
     }
 
     /// Specify screenshot type, defaults to `png`.
@@ -644,6 +893,10 @@ impl<'a> ScreenshotBuilder<'a> {
         self
     }
 
+    deadline! {}
+
+    capture! {}
+
     setter! {
         /// Hides default white background and allows capturing screenshots with transparency. Not applicable to `jpeg` images.
         /// Defaults to `false`.
@@ -664,21 +917,32 @@ impl<'a> ScreenshotBuilder<'a> {
 
 pub struct WaitForSelectorBuilder<'a> {
     inner: Weak<Impl>,
-    args: WaitForSelectorArgs<'a>
+    args: WaitForSelectorArgs<'a>,
+    deadline: Option<Duration>
 }
 
 impl<'a> WaitForSelectorBuilder<'a> {
     pub(crate) fn new(inner: Weak<Impl>, selector: &'a str) -> Self {
         let args = WaitForSelectorArgs::new(selector);
-        Self { inner, args }
+        Self {
+            inner,
+            args,
+            deadline: None
+        }
     }
 
     pub async fn wait_for_selector(self) -> Result<(), Arc<Error>> {
-        let Self { inner, args } = self;
-        let _ = upgrade(&inner)?.wait_for_selector(args).await?;
+        let Self {
+            inner,
+            args,
+            deadline
+        } = self;
+        let _ = with_deadline(deadline, upgrade(&inner)?.wait_for_selector(args)).await?;
         Ok(())
     }
 
+    deadline! {}
+
     setter! {
         state: Option<WaitForSelectorState>,
         timeout: Option<f64>
@@ -688,7 +952,8 @@ impl<'a> WaitForSelectorBuilder<'a> {
 pub struct SelectOptionBuilder {
     inner: Weak<Impl>,
     args: SelectOptionArgs,
-    err: Option<Error>
+    err: Option<Error>,
+    deadline: Option<Duration>
 }
 
 impl SelectOptionBuilder {
@@ -697,16 +962,22 @@ impl SelectOptionBuilder {
         Self {
             inner,
             args,
-            err: None
+            err: None,
+            deadline: None
         }
     }
 
     pub async fn select_option(self) -> Result<Vec<String>, Arc<Error>> {
-        let Self { inner, args, err } = self;
+        let Self {
+            inner,
+            args,
+            err,
+            deadline
+        } = self;
         if let Some(e) = err {
             return Err(e.into());
         }
-        upgrade(&inner)?.select_option(args).await
+        with_deadline(deadline, upgrade(&inner)?.select_option(args)).await
     }
 
     pub fn add_element(mut self, x: &ElementHandle) -> Self {
@@ -758,6 +1029,8 @@ impl SelectOptionBuilder {
         self
     }
 
+    deadline! {}
+
     setter! {
         /// Actions that initiate navigations are waiting for these navigations to happen and for pages to start loading. You can
         /// opt out of waiting via setting this flag. You would only need this option in the exceptional cases such as navigating to
@@ -779,7 +1052,8 @@ impl SelectOptionBuilder {
 
 pub struct SetInputFilesBuilder {
     inner: Weak<Impl>,
-    args: SetInputFilesArgs
+    args: SetInputFilesArgs,
+    deadline: Option<Duration>
 }
 
 impl SetInputFilesBuilder {
@@ -788,12 +1062,20 @@ impl SetInputFilesBuilder {
             files: vec![file],
             ..SetInputFilesArgs::default()
         };
-        Self { inner, args }
+        Self {
+            inner,
+            args,
+            deadline: None
+        }
     }
 
     pub async fn set_input_files(self) -> Result<(), Arc<Error>> {
-        let Self { inner, args } = self;
-        upgrade(&inner)?.set_input_files(args).await
+        let Self {
+            inner,
+            args,
+            deadline
+        } = self;
+        with_deadline(deadline, upgrade(&inner)?.set_input_files(args)).await
     }
 
     pub fn add_file(mut self, x: File) -> Self {
@@ -801,6 +1083,8 @@ impl SetInputFilesBuilder {
         self
     }
 
+    deadline! {}
+
     setter! {
         /// Actions that initiate navigations are waiting for these navigations to happen and for pages to start loading. You can
         /// opt out of waiting via setting this flag. You would only need this option in the exceptional cases such as navigating to
@@ -0,0 +1,357 @@
+//! Web-first assertions that poll until the condition is met or the timeout expires.
+//!
+//! ```ignore
+//! use playwright::expect::expect;
+//! expect(&page.locator("h1")).to_have_text("Welcome").await?;
+//! expect(&page.locator(".spinner")).not().to_be_visible().await?;
+//! expect(&page).to_have_title("Dashboard").await?;
+//! ```
+use crate::{
+    api::{Locator, Page},
+    imp::{core::*, prelude::*}
+};
+use std::{fmt, time::Instant};
+
+const DEFAULT_TIMEOUT: f64 = 5000.;
+const POLLING: [u64; 3] = [100, 250, 500];
+const POLLING_MAX: u64 = 1000;
+
+/// Targets of [`expect`]
+pub trait Subject {
+    fn describe(&self) -> String;
+}
+
+impl Subject for Locator {
+    fn describe(&self) -> String { format!("locator({:?})", self.to_string()) }
+}
+
+impl Subject for Page {
+    fn describe(&self) -> String { "page".into() }
+}
+
+/// Creates assertions on a [`Locator`] or a [`Page`]
+pub fn expect<T: Subject>(target: &T) -> Expect<'_, T> {
+    Expect {
+        target,
+        not: false,
+        timeout: DEFAULT_TIMEOUT
+    }
+}
+
+pub struct Expect<'a, T> {
+    target: &'a T,
+    not: bool,
+    timeout: f64
+}
+
+/// The condition was not met within the timeout
+#[derive(Debug, Clone, thiserror::Error)]
+pub struct AssertionFailure {
+    pub target: String,
+    pub assertion: String,
+    pub not: bool,
+    pub expected: String,
+    /// The value observed last
+    pub received: String,
+    pub timeout: f64
+}
+
+impl<'a, T: Subject> Expect<'a, T> {
+    /// Negates the assertion
+    #[allow(clippy::should_implement_trait)]
+    pub fn not(mut self) -> Self {
+        self.not = !self.not;
+        self
+    }
+
+    /// Time to retry the assertion for in milliseconds. Defaults to `5000`.
+    pub fn timeout(mut self, x: f64) -> Self {
+        self.timeout = x;
+        self
+    }
+
+    async fn poll<V, F, Fut, P>(
+        &self,
+        assertion: String,
+        expected: String,
+        observe: F,
+        pass: P
+    ) -> ArcResult<()>
+    where
+        V: Show,
+        F: Fn() -> Fut,
+        Fut: Future<Output = ArcResult<V>>,
+        P: Fn(&V) -> bool
+    {
+        let started = Instant::now();
+        let timeout = Duration::from_millis(self.timeout as u64);
+        let mut intervals = POLLING
+            .iter()
+            .copied()
+            .chain(std::iter::repeat(POLLING_MAX));
+        loop {
+            let received = match observe().await {
+                Ok(v) if pass(&v) != self.not => return Ok(()),
+                Ok(v) => v.show(),
+                Err(e) if is_transient(&e) => format!("<{}>", e),
+                Err(e) => return Err(e)
+            };
+            let elapsed = started.elapsed();
+            if elapsed >= timeout {
+                return Err(Arc::new(Error::Assertion(AssertionFailure {
+                    target: self.target.describe(),
+                    assertion,
+                    not: self.not,
+                    expected,
+                    received,
+                    timeout: self.timeout
+                })));
+            }
+            let interval = Duration::from_millis(intervals.next().unwrap_or(POLLING_MAX));
+            sleep(interval.min(timeout - elapsed)).await;
+        }
+    }
+}
+
+/// Errors that go away when the page settles, like a navigation destroying the element or its
+/// execution context. Assertions retry them like a value that does not match yet, and locator
+/// actions resolve the element again.
+pub(crate) fn is_transient(e: &Error) -> bool {
+    const MESSAGES: &[&str] = &[
+        "Execution context was destroyed",
+        "Cannot find context with specified id",
+        "Element is not attached to the DOM",
+        "Frame was detached"
+    ];
+    match e {
+        Error::ObjectNotFound => true,
+        Error::Arc(e) => is_transient(e),
+        Error::ErrorResponded(m) => MESSAGES.iter().any(|t| m.message.contains(t)),
+        _ => false
+    }
+}
+
+/// Formats observed values
+trait Show {
+    fn show(&self) -> String;
+}
+
+impl Show for bool {
+    fn show(&self) -> String { self.to_string() }
+}
+
+impl Show for usize {
+    fn show(&self) -> String { self.to_string() }
+}
+
+impl Show for String {
+    fn show(&self) -> String { format!("{:?}", self) }
+}
+
+impl Show for Option<String> {
+    fn show(&self) -> String {
+        match self {
+            Some(s) => s.show(),
+            None => "<not found>".into()
+        }
+    }
+}
+
+fn normalize(s: &str) -> String { s.split_whitespace().collect::<Vec<_>>().join(" ") }
+
+impl<'a> Expect<'a, Locator> {
+    /// The element has the text, ignoring differences of whitespace.
+    pub async fn to_have_text(&self, expected: &str) -> ArcResult<()> {
+        let target = self.target;
+        let expected = normalize(expected);
+        self.poll(
+            "to_have_text".into(),
+            format!("{:?}", expected),
+            move || async move { Ok(text(target).await?.map(|s| normalize(&s))) },
+            |t| t.as_deref() == Some(&*expected)
+        )
+        .await
+    }
+
+    /// The element's text contains the substring, ignoring differences of whitespace.
+    pub async fn to_contain_text(&self, expected: &str) -> ArcResult<()> {
+        let target = self.target;
+        let expected = normalize(expected);
+        self.poll(
+            "to_contain_text".into(),
+            format!("{:?}", expected),
+            move || async move { Ok(text(target).await?.map(|s| normalize(&s))) },
+            |t| t.as_deref().map(|t| t.contains(&*expected)) == Some(true)
+        )
+        .await
+    }
+
+    pub async fn to_have_attribute(&self, name: &str, value: &str) -> ArcResult<()> {
+        let target = self.target;
+        self.poll(
+            format!("to_have_attribute({:?})", name),
+            format!("{:?}", value),
+            move || async move {
+                match target.resolve_now().await? {
+                    Some(e) => e.get_attribute(name).await,
+                    None => Ok(None)
+                }
+            },
+            |a| a.as_deref() == Some(value)
+        )
+        .await
+    }
+
+    pub async fn to_be_visible(&self) -> ArcResult<()> {
+        let target = self.target;
+        self.poll(
+            "to_be_visible".into(),
+            "true".into(),
+            move || target.is_visible(),
+            |v| *v
+        )
+        .await
+    }
+
+    pub async fn to_be_checked(&self) -> ArcResult<()> {
+        let target = self.target;
+        self.poll(
+            "to_be_checked".into(),
+            "true".into(),
+            move || async move {
+                match target.resolve_now().await? {
+                    Some(e) => e.is_checked().await,
+                    None => Ok(false)
+                }
+            },
+            |v| *v
+        )
+        .await
+    }
+
+    pub async fn to_have_count(&self, expected: usize) -> ArcResult<()> {
+        let target = self.target;
+        self.poll(
+            "to_have_count".into(),
+            expected.to_string(),
+            move || target.count(),
+            |n| *n == expected
+        )
+        .await
+    }
+}
+
+async fn text(target: &Locator) -> ArcResult<Option<String>> {
+    match target.resolve_now().await? {
+        Some(e) => e.text_content().await,
+        None => Ok(None)
+    }
+}
+
+impl<'a> Expect<'a, Page> {
+    pub async fn to_have_url(&self, expected: &str) -> ArcResult<()> {
+        let target = self.target;
+        self.poll(
+            "to_have_url".into(),
+            format!("{:?}", expected),
+            move || async move { Ok(target.url()?) },
+            |u| u == expected
+        )
+        .await
+    }
+
+    pub async fn to_have_title(&self, expected: &str) -> ArcResult<()> {
+        let target = self.target;
+        self.poll(
+            "to_have_title".into(),
+            format!("{:?}", expected),
+            move || target.title(),
+            |t| t == expected
+        )
+        .await
+    }
+}
+
+impl fmt::Display for AssertionFailure {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let not = if self.not { "not()." } else { "" };
+        writeln!(
+            f,
+            "expect({}).{}{} failed after {}ms",
+            self.target, not, self.assertion, self.timeout
+        )?;
+        let expected = if self.not {
+            "Expected: not "
+        } else {
+            "Expected: "
+        };
+        writeln!(f, "{:>14}{}", expected, self.expected)?;
+        write!(f, "{:>14}{}", "Received: ", self.received)?;
+        if !self.not {
+            if let Some(i) = first_difference(&self.expected, &self.received) {
+                write!(f, "\n{:>width$}^", "", width = 14 + i)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Char index of the first difference
+fn first_difference(a: &str, b: &str) -> Option<usize> {
+    let mut i = 0;
+    let mut xs = a.chars();
+    let mut ys = b.chars();
+    loop {
+        match (xs.next(), ys.next()) {
+            (None, None) => return None,
+            (Some(x), Some(y)) if x == y => i += 1,
+            _ => return Some(i)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn failure_message() {
+        let e = AssertionFailure {
+            target: "locator(\"h1\")".into(),
+            assertion: "to_have_text".into(),
+            not: false,
+            expected: "\"Hello world\"".into(),
+            received: "\"Hello wrld\"".into(),
+            timeout: 5000.
+        };
+        let s = e.to_string();
+        assert!(s.starts_with("expect(locator(\"h1\")).to_have_text failed after 5000ms"));
+        assert!(s.contains("Expected: \"Hello world\""));
+        assert!(s.contains("Received: \"Hello wrld\""));
+        assert!(s.ends_with(&format!("\n{}^", " ".repeat(14 + 8))));
+        assert_eq!(first_difference("abc", "abd"), Some(2));
+        assert_eq!(first_difference("abc", "abc"), None);
+    }
+
+    #[test]
+    fn transient() {
+        assert!(is_transient(&Error::ObjectNotFound));
+        let responded = |message: &str| {
+            Error::ErrorResponded(Arc::new(ErrorMessage {
+                name: "Error".into(),
+                message: message.into(),
+                stack: String::new()
+            }))
+        };
+        let destroyed =
+            responded("Execution context was destroyed, most likely because of a navigation");
+        assert!(is_transient(&Error::Arc(Arc::new(destroyed))));
+        assert!(!is_transient(&responded("Target closed")));
+        assert!(!is_transient(&Error::StrictModeViolation("h1".into(), 2)));
+    }
+
+    #[test]
+    fn whitespace() {
+        assert_eq!(normalize("  a \n b  "), "a b");
+    }
+}
@@ -0,0 +1,154 @@
+//! Declarative extraction of page data into serde types.
+//!
+//! A [`Field`] tree describes where each value is found. It is evaluated in the page in a single
+//! round-trip and the result is deserialized into the target type.
+//!
+//! ```ignore
+//! #[derive(Deserialize)]
+//! struct Product {
+//!     title: String,
+//!     price: Option<String>,
+//!     tags: Vec<String>
+//! }
+//!
+//! impl Extract for Product {
+//!     fn schema() -> Field {
+//!         Field::object()
+//!             .field("title", Field::text("h1"))
+//!             .field("price", Field::attribute(".price", "data-value"))
+//!             .field("tags", Field::list(".tag", Field::text("")))
+//!     }
+//! }
+//!
+//! let product: Product = page.extract().await?;
+//! ```
+use crate::imp::prelude::*;
+
+/// Types that can be extracted with [`Page::extract`](crate::api::Page::extract)
+pub trait Extract: DeserializeOwned {
+    fn schema() -> Field;
+}
+
+/// Where a value is found. Selectors are CSS and relative to the enclosing element.
+/// An empty selector is the enclosing element itself.
+///
+/// A missing element yields `null`, so use `Option` for fields that may be absent.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "kind", rename_all = "camelCase")]
+pub enum Field {
+    /// Trimmed `textContent`
+    Text { selector: String },
+    InnerText { selector: String },
+    InnerHtml { selector: String },
+    Attribute { selector: String, name: String },
+    /// A map with the given fields, evaluated inside the element matching `selector`
+    Object {
+        selector: String,
+        fields: Vec<(String, Field)>
+    },
+    /// `item` evaluated inside every element matching `selector`.
+    /// An empty selector yields a single item for the enclosing element.
+    List { selector: String, item: Box<Field> }
+}
+
+impl Field {
+    pub fn text(selector: &str) -> Self {
+        Self::Text {
+            selector: selector.into()
+        }
+    }
+
+    pub fn inner_text(selector: &str) -> Self {
+        Self::InnerText {
+            selector: selector.into()
+        }
+    }
+
+    pub fn inner_html(selector: &str) -> Self {
+        Self::InnerHtml {
+            selector: selector.into()
+        }
+    }
+
+    pub fn attribute(selector: &str, name: &str) -> Self {
+        Self::Attribute {
+            selector: selector.into(),
+            name: name.into()
+        }
+    }
+
+    /// An empty object evaluated on the enclosing element. Add fields with [`field`](Field::field).
+    pub fn object() -> Self {
+        Self::Object {
+            selector: String::new(),
+            fields: Vec::new()
+        }
+    }
+
+    pub fn list(selector: &str, item: Field) -> Self {
+        Self::List {
+            selector: selector.into(),
+            item: Box::new(item)
+        }
+    }
+
+    /// Adds a field to an object. Does nothing to other kinds.
+    pub fn field(mut self, name: &str, field: Field) -> Self {
+        if let Self::Object { fields, .. } = &mut self {
+            fields.push((name.into(), field));
+        }
+        self
+    }
+
+    /// Evaluates an object inside the element matching `selector`.
+    pub fn within(mut self, selector: &str) -> Self {
+        if let Self::Object { selector: s, .. } = &mut self {
+            *s = selector.into();
+        }
+        self
+    }
+}
+
+/// Takes `[root, field]`. `root` is `null` for the whole document.
+pub(crate) const EXTRACT: &str = r#"([root, field]) => {
+    const pick = (e, s) => s ? e.querySelector(s) : e;
+    const run = (e, f) => {
+        if (f.kind === 'list')
+            return (f.selector ? Array.from(e.querySelectorAll(f.selector)) : [e]).map(x => run(x, f.item));
+        const t = pick(e, f.selector);
+        if (!t)
+            return null;
+        switch (f.kind) {
+            case 'text': return t.textContent === null ? null : t.textContent.trim();
+            case 'innerText': return t.innerText;
+            case 'innerHtml': return t.innerHTML;
+            case 'attribute': return t.getAttribute(f.name);
+            case 'object': return Object.fromEntries(f.fields.map(([k, v]) => [k, run(t, v)]));
+        }
+    };
+    return run(root || document, field);
+}"#;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn serialize() {
+        let f = Field::object()
+            .within("main")
+            .field("links", Field::list("a", Field::attribute("", "href")));
+        assert_eq!(
+            serde_json::to_value(&f).unwrap(),
+            serde_json::json!({
+                "kind": "object",
+                "selector": "main",
+                "fields": [["links", {
+                    "kind": "list",
+                    "selector": "a",
+                    "item": {"kind": "attribute", "selector": "", "name": "href"}
+                }]]
+            })
+        );
+    }
+}
@@ -0,0 +1,344 @@
+//! Filling and reading HTML forms with serde values.
+//!
+//! Keys are matched to controls by `name`, then `id`, then the text of an associated `<label>`.
+//! The value decides what happens to each kind of control:
+//!
+//! | control                  | value                                                     |
+//! |--------------------------|-----------------------------------------------------------|
+//! | text inputs, `textarea`  | string or number, filled                                  |
+//! | `select`                 | option value or label, or an array of them for `multiple` |
+//! | single checkbox          | bool, or the checkbox value to check it                   |
+//! | group of checkboxes      | array of values to check, the others are unchecked        |
+//! | radio group              | value of the radio to check                               |
+//! | file input               | path or array of paths                                    |
+//!
+//! `null` leaves the control untouched.
+use crate::{
+    api::{ElementHandle, Frame},
+    imp::{core::*, prelude::*, utils::File}
+};
+
+/// Outcome of [`Page::fill_form`](crate::api::Page::fill_form)
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct FillFormReport {
+    /// Keys applied to a control
+    pub filled: Vec<String>,
+    /// Keys without a matching control, or whose value did not fit the control
+    pub unmatched: Vec<String>
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+enum Kind {
+    Text,
+    Select,
+    Checkbox,
+    Radio,
+    File
+}
+
+#[derive(Debug, Deserialize)]
+struct Located {
+    kind: Kind,
+    controls: Vec<Control>
+}
+
+/// A control and the values it accepts. Selects have one entry per option.
+#[derive(Debug, Deserialize)]
+struct Control {
+    index: usize,
+    value: String,
+    #[serde(default)]
+    label: String
+}
+
+const CONTROLS: &str = "css:light=input, select, textarea";
+
+/// Takes `[form, keys]` and returns the matching controls of each key, or `null`.
+/// Indices are into the form's controls in document order, as listed by [`CONTROLS`].
+const LOCATE: &str = r#"([form, keys]) => {
+    const all = Array.from(form.querySelectorAll('input, select, textarea'));
+    const ignored = ['hidden', 'submit', 'button', 'reset', 'image'];
+    const kindOf = e => {
+        if (e.localName === 'select') return 'select';
+        if (e.localName === 'textarea') return 'text';
+        const type = (e.getAttribute('type') || '').toLowerCase();
+        if (ignored.includes(type)) return null;
+        return ['checkbox', 'radio', 'file'].includes(type) ? type : 'text';
+    };
+    const normalize = s => (s || '').replace(/\s+/g, ' ').trim();
+    const byLabel = key => Array.from(form.querySelectorAll('label'))
+        .filter(l => l.control && normalize(l.textContent) === key)
+        .map(l => l.control);
+    const locate = key => {
+        let found = all.filter(e => e.name === key);
+        if (!found.length) found = all.filter(e => e.id === key);
+        if (!found.length) found = byLabel(key);
+        found = found.filter(e => all.includes(e) && kindOf(e));
+        if (!found.length) return null;
+        const kind = kindOf(found[0]);
+        found = found.filter(e => kindOf(e) === kind);
+        if (kind === 'select') {
+            const index = all.indexOf(found[0]);
+            const controls = Array.from(found[0].options)
+                .map(o => ({ index, value: o.value, label: normalize(o.label) }));
+            return { kind, controls };
+        }
+        const controls = found.map(e => ({ index: all.indexOf(e), value: e.value }));
+        return { kind, controls };
+    };
+    return keys.map(locate);
+}"#;
+
+/// Takes the form element and returns its state as an object keyed by `name` or `id`
+const READ: &str = r#"form => {
+    const state = {};
+    const groups = {};
+    for (const e of form.querySelectorAll('input, select, textarea')) {
+        const key = e.name || e.id;
+        const type = (e.getAttribute('type') || '').toLowerCase();
+        if (!key || ['submit', 'button', 'reset', 'image'].includes(type))
+            continue;
+        if (type === 'checkbox') {
+            (groups[key] = groups[key] || []).push(e);
+        } else if (type === 'radio') {
+            if (e.checked) state[key] = e.value;
+            else if (!(key in state)) state[key] = null;
+        } else if (type === 'file') {
+            state[key] = Array.from(e.files || []).map(f => f.name);
+        } else if (e.localName === 'select' && e.multiple) {
+            state[key] = Array.from(e.selectedOptions).map(o => o.value);
+        } else {
+            state[key] = e.value;
+        }
+    }
+    for (const [key, es] of Object.entries(groups))
+        state[key] = es.length === 1 ? es[0].checked : es.filter(e => e.checked).map(e => e.value);
+    return state;
+}"#;
+
+pub(crate) async fn fill(
+    frame: &Frame,
+    selector: &str,
+    value: Value
+) -> ArcResult<FillFormReport> {
+    let map = match value {
+        Value::Object(m) => m,
+        _ => return Err(Error::NotObject.into())
+    };
+    let form = frame
+        .query_selector(selector)
+        .await?
+        .ok_or(Error::ObjectNotFound)?
+        .dispose_on_drop(true);
+    let keys: Vec<&String> = map.keys().collect();
+    let located: Vec<Option<Located>> = frame.evaluate(LOCATE, (&form, &keys)).await?;
+    let elements: Vec<ElementHandle> = form
+        .query_selector_all(CONTROLS)
+        .await?
+        .into_iter()
+        .map(|e| e.dispose_on_drop(true))
+        .collect();
+    let mut report = FillFormReport::default();
+    for ((key, value), located) in map.iter().zip(located) {
+        if value.is_null() {
+            continue;
+        }
+        let applied = match located {
+            Some(l) => match apply(&elements, l, value).await {
+                Ok(applied) => applied,
+                Err(e) if is_rejected(&e) => false,
+                Err(e) => return Err(e)
+            },
+            None => false
+        };
+        if applied {
+            report.filled.push(key.clone());
+        } else {
+            report.unmatched.push(key.clone());
+        }
+    }
+    Ok(report)
+}
+
+pub(crate) async fn read(frame: &Frame, selector: &str) -> ArcResult<Value> {
+    frame.evaluate_on_selector(selector, READ, None::<()>).await
+}
+
+/// Returns false when the value does not fit the control
+async fn apply(elements: &[ElementHandle], located: Located, value: &Value) -> ArcResult<bool> {
+    let Located { kind, controls } = located;
+    let element = |c: &Control| elements.get(c.index).ok_or(Error::ObjectNotFound);
+    match kind {
+        Kind::Text => {
+            let s = match scalar(value) {
+                Some(s) => s,
+                None => return Ok(false)
+            };
+            element(&controls[0])?.fill_builder(&s).fill().await?;
+        }
+        Kind::Select => {
+            let wanted = match strings(value) {
+                Some(w) => w,
+                None => return Ok(false)
+            };
+            let e = element(&controls[0])?;
+            let mut builder = e.select_option_builder();
+            for w in wanted {
+                builder = if controls.iter().any(|c| c.value == w) {
+                    builder.add_value(w)
+                } else if controls.iter().any(|c| c.label == w) {
+                    builder.add_label(w)
+                } else {
+                    return Ok(false);
+                };
+            }
+            builder.select_option().await?;
+        }
+        Kind::Checkbox => {
+            let checked: Vec<bool> = match (value, controls.len()) {
+                (Value::Bool(b), 1) => vec![*b],
+                (Value::String(s), 1) if s == &controls[0].value => vec![true],
+                _ => {
+                    let wanted = match strings(value) {
+                        Some(w) => w,
+                        None => return Ok(false)
+                    };
+                    if !wanted.iter().all(|w| controls.iter().any(|c| &c.value == w)) {
+                        return Ok(false);
+                    }
+                    controls.iter().map(|c| wanted.contains(&c.value)).collect()
+                }
+            };
+            for (c, checked) in controls.iter().zip(checked) {
+                let e = element(c)?;
+                if checked {
+                    e.check_builder().check().await?;
+                } else {
+                    e.uncheck_builder().uncheck().await?;
+                }
+            }
+        }
+        Kind::Radio => {
+            let s = match scalar(value) {
+                Some(s) => s,
+                None => return Ok(false)
+            };
+            match controls.iter().find(|c| c.value == s) {
+                Some(c) => element(c)?.check_builder().check().await?,
+                None => return Ok(false)
+            }
+        }
+        Kind::File => {
+            let paths = match strings(value) {
+                Some(p) if !p.is_empty() => p,
+                _ => return Ok(false)
+            };
+            let mut files = paths.iter().map(|p| read_file(p));
+            let first = files.next().unwrap()?;
+            let e = element(&controls[0])?;
+            let mut builder = e.set_input_files_builder(first);
+            for f in files {
+                builder = builder.add_file(f?);
+            }
+            builder.set_input_files().await?;
+        }
+    }
+    Ok(true)
+}
+
+/// The control refused the value, like `fill` on `<input type=range>`
+fn is_rejected(e: &Error) -> bool {
+    const MESSAGES: &[&str] = &[
+        "cannot be filled",
+        "Cannot type text into",
+        "Malformed value",
+        "Not a checkbox or radio button",
+        "Element is not an <input>",
+        "Element is not a <select>"
+    ];
+    match e {
+        Error::Arc(e) => is_rejected(e),
+        Error::ErrorResponded(m) => MESSAGES.iter().any(|t| m.message.contains(t)),
+        _ => false
+    }
+}
+
+fn scalar(v: &Value) -> Option<String> {
+    match v {
+        Value::String(s) => Some(s.clone()),
+        Value::Number(n) => Some(n.to_string()),
+        _ => None
+    }
+}
+
+/// A scalar or an array of scalars
+fn strings(v: &Value) -> Option<Vec<String>> {
+    match v {
+        Value::Array(xs) => xs.iter().map(scalar).collect(),
+        _ => scalar(v).map(|s| vec![s])
+    }
+}
+
+fn read_file(path: &str) -> Result<File, Error> {
+    let path = Path::new(path);
+    let body = std::fs::read(path)?;
+    let name = path
+        .file_name()
+        .map(|n| n.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    Ok(File::new(name, mime(path).into(), &body))
+}
+
+fn mime(path: &Path) -> &'static str {
+    let ext = path
+        .extension()
+        .and_then(|e| e.to_str())
+        .map(str::to_ascii_lowercase);
+    match ext.as_deref() {
+        Some("txt") => "text/plain",
+        Some("html") | Some("htm") => "text/html",
+        Some("css") => "text/css",
+        Some("js") => "application/javascript",
+        Some("json") => "application/json",
+        Some("csv") => "text/csv",
+        Some("pdf") => "application/pdf",
+        Some("png") => "image/png",
+        Some("jpg") | Some("jpeg") => "image/jpeg",
+        Some("gif") => "image/gif",
+        Some("svg") => "image/svg+xml",
+        Some("webp") => "image/webp",
+        _ => "application/octet-stream"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn values() {
+        assert_eq!(scalar(&serde_json::json!(3)), Some("3".into()));
+        assert_eq!(scalar(&serde_json::json!(true)), None);
+        assert_eq!(
+            strings(&serde_json::json!(["a", 1])),
+            Some(vec!["a".into(), "1".into()])
+        );
+        assert_eq!(strings(&serde_json::json!([{}])), None);
+        assert_eq!(mime(Path::new("a/B.PNG")), "image/png");
+    }
+
+    #[test]
+    fn rejected() {
+        let responded = |message: &str| {
+            Error::ErrorResponded(Arc::new(ErrorMessage {
+                name: "Error".into(),
+                message: message.into(),
+                stack: String::new()
+            }))
+        };
+        let range = responded("Error: Input of type \"range\" cannot be filled");
+        assert!(is_rejected(&Error::Arc(Arc::new(range))));
+        assert!(!is_rejected(&responded("Target closed")));
+    }
+}
@@ -1,6 +1,14 @@
 pub use crate::imp::frame::{FrameNavigatedEvent, FrameState, Polling};
 use crate::{
-    api::{ElementHandle, JsHandle, Page, Response},
+    api::{
+        drag::DragAndDropBuilder,
+        extract::{Extract, Field, EXTRACT},
+        form::{self, FillFormReport},
+        get_by::{AriaRole, GetBy, GetByRole, TextMatch},
+        locator::Scope,
+        table::TableBuilder,
+        ElementHandle, FrameLocator, JsHandle, KeyInput, Locator, Page, Response
+    },
     imp::{
         core::*,
         frame::{
@@ -12,6 +20,7 @@ use crate::{
         utils::{DocumentLoadState, File, KeyboardModifier, MouseButton, Position}
     }
 };
+use std::fmt;
 
 /// At every point of time, page exposes its current frame tree via the [`method: Page.mainFrame`] and
 /// [`method: Frame.childFrames`] methods.
@@ -58,6 +67,32 @@ impl PartialEq for Frame {
     }
 }
 
+/// Snapshot of a frame and its descendants
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FrameTree {
+    pub name: String,
+    pub url: String,
+    pub children: Vec<FrameTree>
+}
+
+impl FrameTree {
+    fn fmt_indented(&self, f: &mut fmt::Formatter<'_>, depth: usize) -> fmt::Result {
+        write!(f, "{}{}", "  ".repeat(depth), self.url)?;
+        if !self.name.is_empty() {
+            write!(f, " ({})", self.name)?;
+        }
+        for c in &self.children {
+            writeln!(f)?;
+            c.fmt_indented(f, depth + 1)?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for FrameTree {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result { self.fmt_indented(f, 0) }
+}
+
 macro_rules! is_checked {
     ($f: ident) => {
         pub async fn $f(&self, selector: &str, timeout: Option<f64>) -> ArcResult<bool> {
@@ -95,6 +130,28 @@ impl Frame {
             .collect())
     }
 
+    /// Returns `true` if the frame has been detached, or `false` otherwise.
+    pub fn is_detached(&self) -> bool {
+        self.inner
+            .upgrade()
+            .map(|f| f.is_detached())
+            .unwrap_or(true)
+    }
+
+    /// This frame and its descendants. `Display` prints them indented for debugging.
+    pub fn tree(&self) -> Result<FrameTree, Error> {
+        let children = self
+            .child_frames()?
+            .iter()
+            .map(Frame::tree)
+            .collect::<Result<_, _>>()?;
+        Ok(FrameTree {
+            name: self.name()?,
+            url: self.url()?,
+            children
+        })
+    }
+
     /// Returns the main resource response. In case of multiple redirects, the navigation will resolve with the response of the
     /// last redirect.
     ///
@@ -221,6 +278,20 @@ impl Frame {
             .map(ElementHandle::new))
     }
 
+    /// Returns a locator that resolves `selector` in this frame on every action.
+    pub fn locator(&self, selector: &str) -> Locator {
+        Locator::in_frame(self.inner.clone(), selector)
+    }
+
+    /// Returns a frame locator that enters the iframe matching `selector` in this frame on every action.
+    pub fn frame_locator(&self, selector: &str) -> FrameLocator {
+        FrameLocator::new(self.locator(selector))
+    }
+
+    pub(crate) fn scope(&self) -> Scope { Scope::Frame(self.inner.clone()) }
+
+    get_by! {}
+
     pub async fn query_selector_all(&self, selector: &str) -> ArcResult<Vec<ElementHandle>> {
         let es = upgrade(&self.inner)?.query_selector_all(selector).await?;
         Ok(es.into_iter().map(ElementHandle::new).collect())
@@ -283,7 +354,7 @@ impl Frame {
     /// await frame.type('#mytextarea', 'World', {delay: 100}); // Types slower, like a user
     /// ```
     pub fn type_builder<'a, 'b>(&self, selector: &'a str, text: &'b str) -> TypeBuilder<'a, 'b> {
-        TypeBuilder::new(self.inner.clone(), selector, text)
+        TypeBuilder::new(self.inner.clone(), selector, text.into())
     }
 
     /// `key` can specify the intended [keyboardEvent.key](https://developer.mozilla.org/en-US/docs/Web/API/KeyboardEvent/key)
@@ -302,7 +373,12 @@ impl Frame {
     ///
     /// Shortcuts such as `key: "Control+o"` or `key: "Control+Shift+T"` are supported as well. When specified with the
     /// modifier, modifier is pressed and being held while the subsequent key is being pressed.
-    pub fn press_builder<'a, 'b>(&self, selector: &'a str, key: &'b str) -> PressBuilder<'a, 'b> {
+    pub fn press_builder<'a>(
+        &self,
+        selector: &'a str,
+        key: impl Into<KeyInput>
+    ) -> PressBuilder<'a, 'static> {
+        let key = key.into().into_string().into();
         PressBuilder::new(self.inner.clone(), selector, key)
     }
 
@@ -486,6 +562,53 @@ impl Frame {
         upgrade(&self.inner)?.evaluate(expression, Some(arg)).await
     }
 
+    /// Extracts `T` from the document in one round-trip. See [`extract`](crate::api::extract).
+    pub async fn extract<T: Extract>(&self) -> ArcResult<T> {
+        self.extract_with(&T::schema()).await
+    }
+
+    /// Extracts data described by `schema` from the document in one round-trip.
+    pub async fn extract_with<U>(&self, schema: &Field) -> ArcResult<U>
+    where
+        U: DeserializeOwned
+    {
+        self.evaluate(EXTRACT, (None::<&ElementHandle>, schema)).await
+    }
+
+    /// Fills the form matching `form_selector` from the fields of `value`. See [`form`](crate::api::form).
+    pub async fn fill_form<T>(&self, form_selector: &str, value: &T) -> ArcResult<FillFormReport>
+    where
+        T: Serialize
+    {
+        let value = serde_json::to_value(value).map_err(Error::from)?;
+        form::fill(self, form_selector, value).await
+    }
+
+    /// Current state of the form matching `form_selector`, keyed by control `name` or `id`.
+    /// Groups of checkboxes are arrays of the checked values and a single checkbox is a bool.
+    pub async fn read_form(&self, form_selector: &str) -> ArcResult<Value> {
+        form::read(self, form_selector).await
+    }
+
+    /// Rows of the table matching `selector`, keyed by header. See [`table`](crate::api::table).
+    pub async fn extract_table(&self, selector: &str) -> ArcResult<Vec<HashMap<String, String>>> {
+        self.extract_table_builder(selector).rows().await
+    }
+
+    pub fn extract_table_builder<'a>(&self, selector: &'a str) -> TableBuilder<'a> {
+        TableBuilder::new(self.clone(), selector)
+    }
+
+    /// Drags the element matching `source` onto the element matching `target`.
+    /// See [`drag`](crate::api::drag).
+    pub fn drag_and_drop_builder<'a>(
+        &self,
+        source: &'a str,
+        target: &'a str
+    ) -> DragAndDropBuilder<'a> {
+        DragAndDropBuilder::new(self.clone(), source, target)
+    }
+
     /// Returns the return value of `expression`.
     ///
     /// The method finds an element matching the specified selector within the frame and passes it as a first argument to
@@ -665,21 +788,32 @@ impl From<Evt> for Event {
 
 pub struct GotoBuilder<'a, 'b> {
     inner: Weak<Impl>,
-    args: GotoArgs<'a, 'b>
+    args: GotoArgs<'a, 'b>,
+    deadline: Option<Duration>
 }
 
 impl<'a, 'b> GotoBuilder<'a, 'b> {
     pub(crate) fn new(inner: Weak<Impl>, url: &'a str) -> Self {
         let args = GotoArgs::new(url);
-        Self { inner, args }
+        Self {
+            inner,
+            args,
+            deadline: None
+        }
     }
 
     pub async fn goto(self) -> Result<Option<Response>, Arc<Error>> {
-        let Self { inner, args } = self;
-        let r = upgrade(&inner)?.goto(args).await?;
+        let Self {
+            inner,
+            args,
+            deadline
+        } = self;
+        let r = with_deadline(deadline, upgrade(&inner)?.goto(args)).await?;
         Ok(r.map(Response::new))
     }
 
+    deadline! {}
+
     setter! {
         /// Referer header value. If provided it will take preference over the referer header value set by
         /// [`method: Page.setExtraHTTPHeaders`].
@@ -693,21 +827,32 @@ macro_rules! clicker {
     ($t: ident, $f: ident) => {
         pub struct $t<'a> {
             inner: Weak<Impl>,
-            args: ClickArgs<'a>
+            args: ClickArgs<'a>,
+            deadline: Option<Duration>
         }
 
         impl<'a> $t<'a> {
             pub(crate) fn new(inner: Weak<Impl>, selector: &'a str) -> Self {
                 let args = ClickArgs::new(selector);
-                Self { inner, args }
+                Self {
+                    inner,
+                    args,
+                    deadline: None
+                }
             }
 
             pub async fn $f(self) -> Result<(), Arc<Error>> {
-                let Self { inner, args } = self;
-                let _ = upgrade(&inner)?.$f(args).await?;
+                let Self {
+                    inner,
+                    args,
+                    deadline
+                } = self;
+                let _ = with_deadline(deadline, upgrade(&inner)?.$f(args)).await?;
                 Ok(())
             }
 
+            deadline! {}
+
             setter! {
                 /// Defaults to `left`.
                 button: Option<MouseButton>,
@@ -741,21 +886,32 @@ clicker!(DblClickBuilder, dblclick);
 
 pub struct WaitForSelectorBuilder<'a> {
     inner: Weak<Impl>,
-    args: WaitForSelectorArgs<'a>
+    args: WaitForSelectorArgs<'a>,
+    deadline: Option<Duration>
 }
 
 impl<'a> WaitForSelectorBuilder<'a> {
     pub(crate) fn new(inner: Weak<Impl>, selector: &'a str) -> Self {
         let args = WaitForSelectorArgs::new(selector);
-        Self { inner, args }
+        Self {
+            inner,
+            args,
+            deadline: None
+        }
     }
 
     pub async fn wait_for_selector(self) -> Result<Option<ElementHandle>, Arc<Error>> {
-        let Self { inner, args } = self;
-        let e = upgrade(&inner)?.wait_for_selector(args).await?;
+        let Self {
+            inner,
+            args,
+            deadline
+        } = self;
+        let e = with_deadline(deadline, upgrade(&inner)?.wait_for_selector(args)).await?;
         Ok(e.map(ElementHandle::new))
     }
 
+    deadline! {}
+
     setter! {
         /// Defaults to `'visible'`.
         state: Option<FrameState>,
@@ -767,21 +923,32 @@ macro_rules! type_builder {
     ($t: ident, $a: ident, $f: ident, $m: ident) => {
         pub struct $t<'a, 'b> {
             inner: Weak<Impl>,
-            args: $a<'a, 'b>
+            args: $a<'a, 'b>,
+            deadline: Option<Duration>
         }
 
         impl<'a, 'b> $t<'a, 'b> {
-            pub(crate) fn new(inner: Weak<Impl>, selector: &'a str, $f: &'b str) -> Self {
+            pub(crate) fn new(inner: Weak<Impl>, selector: &'a str, $f: Cow<'b, str>) -> Self {
                 let args = $a::new(selector, $f);
-                Self { inner, args }
+                Self {
+                    inner,
+                    args,
+                    deadline: None
+                }
             }
 
             pub async fn $m(self) -> Result<(), Arc<Error>> {
-                let Self { inner, args } = self;
-                let _ = upgrade(&inner)?.$m(args).await?;
+                let Self {
+                    inner,
+                    args,
+                    deadline
+                } = self;
+                let _ = with_deadline(deadline, upgrade(&inner)?.$m(args)).await?;
                 Ok(())
             }
 
+            deadline! {}
+
             setter! {
                 /// Time to wait between `keydown` and `keyup` in milliseconds. Defaults to 0.
                 delay: Option<f64>,
@@ -800,20 +967,31 @@ type_builder!(PressBuilder, PressArgs, key, press);
 
 pub struct HoverBuilder<'a> {
     inner: Weak<Impl>,
-    args: HoverArgs<'a>
+    args: HoverArgs<'a>,
+    deadline: Option<Duration>
 }
 
 impl<'a> HoverBuilder<'a> {
     pub(crate) fn new(inner: Weak<Impl>, selector: &'a str) -> Self {
         let args = HoverArgs::new(selector);
-        Self { inner, args }
+        Self {
+            inner,
+            args,
+            deadline: None
+        }
     }
 
     pub async fn goto(self) -> Result<(), Arc<Error>> {
-        let Self { inner, args } = self;
-        upgrade(&inner)?.hover(args).await
+        let Self {
+            inner,
+            args,
+            deadline
+        } = self;
+        with_deadline(deadline, upgrade(&inner)?.hover(args)).await
     }
 
+    deadline! {}
+
     setter! {
         /// Whether to bypass the actionability checks. Defaults to `false`.
         force: Option<bool>,
@@ -832,20 +1010,31 @@ impl<'a> HoverBuilder<'a> {
 
 pub struct SetContentBuilder<'a> {
     inner: Weak<Impl>,
-    args: SetContentArgs<'a>
+    args: SetContentArgs<'a>,
+    deadline: Option<Duration>
 }
 
 impl<'a> SetContentBuilder<'a> {
     pub(crate) fn new(inner: Weak<Impl>, html: &'a str) -> Self {
         let args = SetContentArgs::new(html);
-        Self { inner, args }
+        Self {
+            inner,
+            args,
+            deadline: None
+        }
     }
 
     pub async fn set_content(self) -> Result<(), Arc<Error>> {
-        let Self { inner, args } = self;
-        upgrade(&inner)?.set_content(args).await
+        let Self {
+            inner,
+            args,
+            deadline
+        } = self;
+        with_deadline(deadline, upgrade(&inner)?.set_content(args)).await
     }
 
+    deadline! {}
+
     setter! {
         timeout: Option<f64>,
         wait_until: Option<DocumentLoadState>
@@ -854,21 +1043,32 @@ impl<'a> SetContentBuilder<'a> {
 
 pub struct TapBuilder<'a> {
     inner: Weak<Impl>,
-    args: TapArgs<'a>
+    args: TapArgs<'a>,
+    deadline: Option<Duration>
 }
 
 impl<'a> TapBuilder<'a> {
     pub(crate) fn new(inner: Weak<Impl>, selector: &'a str) -> Self {
         let args = TapArgs::new(selector);
-        Self { inner, args }
+        Self {
+            inner,
+            args,
+            deadline: None
+        }
     }
 
     pub async fn tap(self) -> Result<(), Arc<Error>> {
-        let Self { inner, args } = self;
-        let _ = upgrade(&inner)?.tap(args).await?;
+        let Self {
+            inner,
+            args,
+            deadline
+        } = self;
+        let _ = with_deadline(deadline, upgrade(&inner)?.tap(args)).await?;
         Ok(())
     }
 
+    deadline! {}
+
     setter! {
         /// Whether to bypass the actionability checks. Defaults to `false`.
         force: Option<bool>,
@@ -891,21 +1091,32 @@ impl<'a> TapBuilder<'a> {
 
 pub struct FillBuilder<'a, 'b> {
     inner: Weak<Impl>,
-    args: FillArgs<'a, 'b>
+    args: FillArgs<'a, 'b>,
+    deadline: Option<Duration>
 }
 
 impl<'a, 'b> FillBuilder<'a, 'b> {
     pub(crate) fn new(inner: Weak<Impl>, selector: &'a str, value: &'b str) -> Self {
         let args = FillArgs::new(selector, value);
-        Self { inner, args }
+        Self {
+            inner,
+            args,
+            deadline: None
+        }
     }
 
     pub async fn fill(self) -> Result<(), Arc<Error>> {
-        let Self { inner, args } = self;
-        let _ = upgrade(&inner)?.fill(args).await?;
+        let Self {
+            inner,
+            args,
+            deadline
+        } = self;
+        let _ = with_deadline(deadline, upgrade(&inner)?.fill(args)).await?;
         Ok(())
     }
 
+    deadline! {}
+
     setter! {
         /// Actions that initiate navigations are waiting for these navigations to happen and for pages to start loading. You can
         /// opt out of waiting via setting this flag. You would only need this option in the exceptional cases such as navigating to
@@ -919,21 +1130,32 @@ macro_rules! check_builder {
     ($t: ident, $m: ident) => {
         pub struct $t<'a> {
             inner: Weak<Impl>,
-            args: CheckArgs<'a>
+            args: CheckArgs<'a>,
+            deadline: Option<Duration>
         }
 
         impl<'a> $t<'a> {
             pub(crate) fn new(inner: Weak<Impl>, selector: &'a str) -> Self {
                 let args = CheckArgs::new(selector);
-                Self { inner, args }
+                Self {
+                    inner,
+                    args,
+                    deadline: None
+                }
             }
 
             pub async fn $m(self) -> Result<(), Arc<Error>> {
-                let Self { inner, args } = self;
-                let _ = upgrade(&inner)?.$m(args).await?;
+                let Self {
+                    inner,
+                    args,
+                    deadline
+                } = self;
+                let _ = with_deadline(deadline, upgrade(&inner)?.$m(args)).await?;
                 Ok(())
             }
 
+            deadline! {}
+
             setter! {
                 /// A point to use relative to the top-left corner of element padding box. If not specified, uses some visible point of the element.
                 position: Option<Position>,
@@ -957,20 +1179,27 @@ check_builder!(UncheckBuilder, uncheck);
 
 pub struct AddScriptTagBuilder<'a, 'b, 'c> {
     inner: Weak<Impl>,
-    args: AddScriptTagArgs<'a, 'b, 'c>
+    args: AddScriptTagArgs<'a, 'b, 'c>,
+    deadline: Option<Duration>
 }
 
 impl<'a, 'b, 'c> AddScriptTagBuilder<'a, 'b, 'c> {
     pub(crate) fn new(inner: Weak<Impl>, content: &'a str) -> Self {
         let args = AddScriptTagArgs::new(content);
-        Self { inner, args }
+        Self {
+            inner,
+            args,
+            deadline: None
+        }
     }
 
     pub async fn add_script_tag(self) -> Result<ElementHandle, Arc<Error>> {
-        let Self { inner, args } = self;
-        upgrade(&inner)?
-            .add_script_tag(args)
-            .await
+        let Self {
+            inner,
+            args,
+            deadline
+        } = self;
+        with_deadline(deadline, upgrade(&inner)?.add_script_tag(args)).await
             .map(ElementHandle::new)
     }
 
@@ -981,6 +1210,8 @@ impl<'a, 'b, 'c> AddScriptTagBuilder<'a, 'b, 'c> {
         self
     }
 
+    deadline! {}
+
     setter! {
         /// URL of a script to be added.
         url: Option<&'b str>
@@ -995,7 +1226,8 @@ impl<'a, 'b, 'c> AddScriptTagBuilder<'a, 'b, 'c> {
 pub struct SelectOptionBuilder<'a> {
     inner: Weak<Impl>,
     args: SelectOptionArgs<'a>,
-    err: Option<Error>
+    err: Option<Error>,
+    deadline: Option<Duration>
 }
 
 impl<'a> SelectOptionBuilder<'a> {
@@ -1004,16 +1236,22 @@ impl<'a> SelectOptionBuilder<'a> {
         Self {
             inner,
             args,
-            err: None
+            err: None,
+            deadline: None
         }
     }
 
     pub async fn select_option(self) -> Result<Vec<String>, Arc<Error>> {
-        let Self { inner, args, err } = self;
+        let Self {
+            inner,
+            args,
+            err,
+            deadline
+        } = self;
         if let Some(e) = err {
             return Err(e.into());
         }
-        upgrade(&inner)?.select_option(args).await
+        with_deadline(deadline, upgrade(&inner)?.select_option(args)).await
     }
 
     pub fn add_element(mut self, x: &ElementHandle) -> Self {
@@ -1065,6 +1303,8 @@ impl<'a> SelectOptionBuilder<'a> {
         self
     }
 
+    deadline! {}
+
     setter! {
         /// Actions that initiate navigations are waiting for these navigations to happen and for pages to start loading. You can
         /// opt out of waiting via setting this flag. You would only need this option in the exceptional cases such as navigating to
@@ -1086,19 +1326,28 @@ impl<'a> SelectOptionBuilder<'a> {
 
 pub struct SetInputFilesBuilder<'a> {
     inner: Weak<Impl>,
-    args: SetInputFilesArgs<'a>
+    args: SetInputFilesArgs<'a>,
+    deadline: Option<Duration>
 }
 
 impl<'a> SetInputFilesBuilder<'a> {
     pub(crate) fn new(inner: Weak<Impl>, selector: &'a str, file: File) -> Self {
         let mut args = SetInputFilesArgs::new(selector);
         args.files = vec![file];
-        Self { inner, args }
+        Self {
+            inner,
+            args,
+            deadline: None
+        }
     }
 
     pub async fn set_input_files(self) -> Result<(), Arc<Error>> {
-        let Self { inner, args } = self;
-        upgrade(&inner)?.set_input_files(args).await
+        let Self {
+            inner,
+            args,
+            deadline
+        } = self;
+        with_deadline(deadline, upgrade(&inner)?.set_input_files(args)).await
     }
 
     pub fn add_file(mut self, x: File) -> Self {
@@ -1106,6 +1355,8 @@ impl<'a> SetInputFilesBuilder<'a> {
         self
     }
 
+    deadline! {}
+
     setter! {
         /// Actions that initiate navigations are waiting for these navigations to happen and for pages to start loading. You can
         /// opt out of waiting via setting this flag. You would only need this option in the exceptional cases such as navigating to
@@ -1123,7 +1374,8 @@ impl<'a> SetInputFilesBuilder<'a> {
 pub struct WaitForFunctionBuilder<'a> {
     inner: Weak<Impl>,
     args: WaitForFunctionArgs<'a>,
-    err: Option<Error>
+    err: Option<Error>,
+    deadline: Option<Duration>
 }
 
 impl<'a> WaitForFunctionBuilder<'a> {
@@ -1132,18 +1384,22 @@ impl<'a> WaitForFunctionBuilder<'a> {
         Self {
             inner,
             args,
-            err: None
+            err: None,
+            deadline: None
         }
     }
 
     pub async fn wait_for_function(self) -> Result<JsHandle, Arc<Error>> {
-        let Self { inner, args, err } = self;
+        let Self {
+            inner,
+            args,
+            err,
+            deadline
+        } = self;
         if let Some(e) = err {
             return Err(e.into());
         }
-        upgrade(&inner)?
-            .wait_for_function(args)
-            .await
+        with_deadline(deadline, upgrade(&inner)?.wait_for_function(args)).await
             .map(JsHandle::new)
     }
 
@@ -1162,6 +1418,8 @@ impl<'a> WaitForFunctionBuilder<'a> {
         self
     }
 
+    deadline! {}
+
     setter! {
         /// If `polling` is `'raf'`, then `expression` is constantly executed in `requestAnimationFrame` callback. If `polling` is a
         /// number, then it is treated as an interval in milliseconds at which the function would be executed. Defaults to `raf`.
@@ -0,0 +1,194 @@
+use crate::{
+    api::{
+        get_by::{test_id_attribute, AriaRole, GetBy, GetByRole},
+        ElementHandle, Frame
+    },
+    imp::{core::*, prelude::*}
+};
+
+/// Facts about the element collected in the page
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Hints {
+    test_id: Option<String>,
+    role: Option<String>,
+    name: Option<String>,
+    text: Option<String>,
+    id: Option<String>,
+    css: Vec<String>,
+    path: String
+}
+
+const HINTS: &str = r#"([e, testIdAttribute]) => {
+    const normalize = s => (s || '').replace(/\s+/g, ' ').trim();
+    const quote = s => '"' + s.replace(/["\\]/g, '\\$&').replace(/\n/g, '\\a ') + '"';
+    // Looks generated, like "ember123" or "css-1x2y3z"
+    const unstable = s => /\d{3,}/.test(s) || /^[a-z]+-[0-9a-z]{5,}$/i.test(s);
+    const tag = e.localName;
+    const type = (e.getAttribute('type') || '').toLowerCase();
+    const implicitRole = () => {
+        switch (tag) {
+            case 'button': case 'summary': return 'button';
+            case 'a': case 'area': return e.hasAttribute('href') ? 'link' : null;
+            case 'input':
+                if (['button', 'submit', 'reset', 'image'].includes(type)) return 'button';
+                if (['', 'text', 'email', 'tel', 'url'].includes(type)) return 'textbox';
+                return { checkbox: 'checkbox', radio: 'radio', search: 'searchbox', number: 'spinbutton', range: 'slider' }[type] || null;
+            case 'textarea': return 'textbox';
+            case 'select': return e.multiple ? 'listbox' : 'combobox';
+            case 'h1': case 'h2': case 'h3': case 'h4': case 'h5': case 'h6': return 'heading';
+            case 'img': return e.getAttribute('alt') === '' ? null : 'img';
+            case 'li': return 'listitem';
+            case 'option': return 'option';
+            case 'nav': return 'navigation';
+            default: return null;
+        }
+    };
+    const role = normalize(e.getAttribute('role')).split(' ')[0] || implicitRole();
+    const own = normalize(e.textContent);
+    const name = normalize(e.getAttribute('aria-label')) || normalize(e.getAttribute('alt')) || own;
+    const css = [];
+    for (const attr of ['name', 'placeholder', 'title', 'alt']) {
+        const v = e.getAttribute(attr);
+        if (v) css.push(`${tag}[${attr}=${quote(v)}]`);
+    }
+    const classes = Array.from(e.classList).filter(c => !unstable(c)).map(c => '.' + CSS.escape(c));
+    for (const c of classes) css.push(tag + c);
+    if (classes.length > 1) css.push(tag + classes.join(''));
+    const steps = [];
+    for (let n = e; n && n.nodeType === Node.ELEMENT_NODE; n = n.parentElement) {
+        if (n !== e && n.id && !unstable(n.id)) {
+            steps.unshift('#' + CSS.escape(n.id));
+            break;
+        }
+        const same = n.parentElement ? Array.from(n.parentElement.children).filter(s => s.localName === n.localName) : [n];
+        steps.unshift(same.length > 1 ? `${n.localName}:nth-of-type(${same.indexOf(n) + 1})` : n.localName);
+    }
+    return {
+        testId: e.getAttribute(testIdAttribute),
+        role,
+        name: name && name.length <= 80 ? name : null,
+        text: own && own.length <= 80 && !e.children.length ? own : null,
+        id: e.id && !unstable(e.id) ? '#' + CSS.escape(e.id) : null,
+        css,
+        path: steps.join(' > ')
+    };
+}"#;
+
+const UNIQUE: &str = "(es, e) => es.length === 1 && es[0] === e";
+
+/// Unique selectors of `e` in `frame`, best first
+pub(crate) async fn generate(e: &ElementHandle, frame: &Frame) -> ArcResult<Vec<String>> {
+    let scope = e.scope();
+    let attribute = test_id_attribute(&scope);
+    let hints: Hints = frame.evaluate(HINTS, (e, attribute)).await?;
+    let Hints {
+        test_id,
+        role,
+        name,
+        text,
+        id,
+        css,
+        path
+    } = hints;
+    let mut candidates = Vec::new();
+    if let Some(t) = test_id.filter(|t| !t.is_empty()) {
+        candidates.push(GetBy::test_id(scope.clone(), &t).selector());
+    }
+    if let (Some(role), Some(name)) = (role.as_deref().and_then(AriaRole::from_name), &name) {
+        candidates.push(
+            GetByRole::new(scope.clone(), role)
+                .name(name)
+                .exact(true)
+                .selector()
+        );
+    }
+    candidates.extend(id);
+    if let Some(t) = text {
+        candidates.push(GetBy::text(scope, t.into()).exact(true).selector());
+    }
+    candidates.extend(css);
+    candidates.push(path);
+    let mut unique = Vec::new();
+    for c in candidates {
+        if unique.contains(&c) {
+            continue;
+        }
+        // Invalid or ambiguous candidates are just skipped
+        let ok: bool = match frame.evaluate_on_selector_all(&c, UNIQUE, Some(e)).await {
+            Ok(ok) => ok,
+            Err(err) if is_invalid_selector(&err) => false,
+            Err(err) => return Err(err)
+        };
+        if ok {
+            unique.push(c);
+        }
+    }
+    Ok(unique)
+}
+
+/// The driver rejected the selector, e.g. a CSS syntax this browser does not support
+fn is_invalid_selector(e: &Error) -> bool {
+    const MESSAGES: &[&str] = &[
+        "while parsing selector",
+        "is not a valid selector",
+        "is not a valid XPath expression",
+        "Malformed selector",
+        "Unknown engine"
+    ];
+    match e {
+        Error::InvalidSelector(_) => true,
+        Error::Arc(e) => is_invalid_selector(e),
+        Error::ErrorResponded(m) => MESSAGES.iter().any(|t| m.message.contains(t)),
+        _ => false
+    }
+}
+
+/// Selectors from the main frame down to `e`, one for each iframe element on the way
+pub(crate) async fn chain(e: &ElementHandle) -> ArcResult<Option<Vec<String>>> {
+    let mut chain = Vec::new();
+    let mut current: Option<ElementHandle> = None;
+    loop {
+        let element = current.as_ref().unwrap_or(e);
+        let frame = match element.owner_frame().await? {
+            Some(f) => f,
+            None => return Ok(None)
+        };
+        match generate(element, &frame).await?.into_iter().next() {
+            Some(s) => chain.push(s),
+            None => return Ok(None)
+        }
+        if frame.parent_frame()?.is_none() {
+            break;
+        }
+        current = Some(frame.frame_element().await?.dispose_on_drop(true));
+    }
+    chain.reverse();
+    Ok(Some(chain))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn invalid_selector() {
+        let responded = |message: &str| {
+            Error::ErrorResponded(Arc::new(ErrorMessage {
+                name: "Error".into(),
+                message: message.into(),
+                stack: String::new()
+            }))
+        };
+        let malformed = responded("'div:is(' is not a valid selector.");
+        assert!(is_invalid_selector(&Error::Arc(Arc::new(malformed))));
+        assert!(is_invalid_selector(&responded(
+            "Unknown engine \"tag\" while parsing selector tag=a"
+        )));
+        assert!(!is_invalid_selector(&responded("Target closed")));
+        assert!(!is_invalid_selector(&responded(
+            "Timeout 30000ms exceeded.\n=== logs ===\nwaiting for selector \"#a\""
+        )));
+        assert!(!is_invalid_selector(&Error::ObjectNotFound));
+    }
+}
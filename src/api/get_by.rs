@@ -0,0 +1,573 @@
+//! Builders of selectors that find elements the way users perceive them.
+//!
+//! Every builder renders to a plain selector string with [`selector`](GetByRole::selector),
+//! so it can be passed wherever a selector is accepted, or turned into a [`Locator`].
+//!
+//! ```ignore
+//! let submit = page.get_by_role(AriaRole::Button).name("Submit").selector();
+//! page.click_builder(&submit).click().await?;
+//! page.get_by_label("Email").locator().fill_builder("a@example.com").fill().await?;
+//! ```
+use crate::{
+    api::{locator::Scope, Locator},
+    imp::core::*
+};
+use std::fmt;
+
+/// WAI-ARIA roles. Elements match by explicit `role` attribute or by their implicit role.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum AriaRole {
+    Alert,
+    Article,
+    Banner,
+    Button,
+    Cell,
+    Checkbox,
+    Columnheader,
+    Combobox,
+    Complementary,
+    Contentinfo,
+    Dialog,
+    Form,
+    Grid,
+    Group,
+    Heading,
+    Img,
+    Link,
+    List,
+    Listbox,
+    Listitem,
+    Main,
+    Menu,
+    Menuitem,
+    Navigation,
+    Option,
+    Progressbar,
+    Radio,
+    Region,
+    Row,
+    Rowheader,
+    Searchbox,
+    Separator,
+    Slider,
+    Spinbutton,
+    Status,
+    Switch,
+    Tab,
+    Table,
+    Tabpanel,
+    Textbox,
+    Tooltip
+}
+
+impl AriaRole {
+    const ALL: [Self; 41] = [
+        Self::Alert,
+        Self::Article,
+        Self::Banner,
+        Self::Button,
+        Self::Cell,
+        Self::Checkbox,
+        Self::Columnheader,
+        Self::Combobox,
+        Self::Complementary,
+        Self::Contentinfo,
+        Self::Dialog,
+        Self::Form,
+        Self::Grid,
+        Self::Group,
+        Self::Heading,
+        Self::Img,
+        Self::Link,
+        Self::List,
+        Self::Listbox,
+        Self::Listitem,
+        Self::Main,
+        Self::Menu,
+        Self::Menuitem,
+        Self::Navigation,
+        Self::Option,
+        Self::Progressbar,
+        Self::Radio,
+        Self::Region,
+        Self::Row,
+        Self::Rowheader,
+        Self::Searchbox,
+        Self::Separator,
+        Self::Slider,
+        Self::Spinbutton,
+        Self::Status,
+        Self::Switch,
+        Self::Tab,
+        Self::Table,
+        Self::Tabpanel,
+        Self::Textbox,
+        Self::Tooltip
+    ];
+
+    pub(crate) fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.iter().copied().find(|r| r.as_str() == name)
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Alert => "alert",
+            Self::Article => "article",
+            Self::Banner => "banner",
+            Self::Button => "button",
+            Self::Cell => "cell",
+            Self::Checkbox => "checkbox",
+            Self::Columnheader => "columnheader",
+            Self::Combobox => "combobox",
+            Self::Complementary => "complementary",
+            Self::Contentinfo => "contentinfo",
+            Self::Dialog => "dialog",
+            Self::Form => "form",
+            Self::Grid => "grid",
+            Self::Group => "group",
+            Self::Heading => "heading",
+            Self::Img => "img",
+            Self::Link => "link",
+            Self::List => "list",
+            Self::Listbox => "listbox",
+            Self::Listitem => "listitem",
+            Self::Main => "main",
+            Self::Menu => "menu",
+            Self::Menuitem => "menuitem",
+            Self::Navigation => "navigation",
+            Self::Option => "option",
+            Self::Progressbar => "progressbar",
+            Self::Radio => "radio",
+            Self::Region => "region",
+            Self::Row => "row",
+            Self::Rowheader => "rowheader",
+            Self::Searchbox => "searchbox",
+            Self::Separator => "separator",
+            Self::Slider => "slider",
+            Self::Spinbutton => "spinbutton",
+            Self::Status => "status",
+            Self::Switch => "switch",
+            Self::Tab => "tab",
+            Self::Table => "table",
+            Self::Tabpanel => "tabpanel",
+            Self::Textbox => "textbox",
+            Self::Tooltip => "tooltip"
+        }
+    }
+
+    /// CSS of elements having this role without a `role` attribute
+    fn implicit(&self) -> Option<&'static str> {
+        let css = match self {
+            Self::Article => "article",
+            Self::Banner => "header",
+            Self::Button => {
+                "button, input[type=button], input[type=submit], input[type=reset], input[type=image], summary"
+            }
+            Self::Cell => "td",
+            Self::Checkbox => "input[type=checkbox]",
+            Self::Columnheader => "th:not([scope=row])",
+            Self::Combobox => "select:not([multiple])",
+            Self::Complementary => "aside",
+            Self::Contentinfo => "footer",
+            Self::Dialog => "dialog",
+            Self::Form => "form",
+            Self::Group => "fieldset, details",
+            Self::Heading => "h1, h2, h3, h4, h5, h6",
+            Self::Img => "img:not([alt=\"\"])",
+            Self::Link => "a[href], area[href]",
+            Self::List => "ul, ol",
+            Self::Listbox => "select[multiple], datalist",
+            Self::Listitem => "li",
+            Self::Main => "main",
+            Self::Navigation => "nav",
+            Self::Option => "option",
+            Self::Progressbar => "progress",
+            Self::Radio => "input[type=radio]",
+            Self::Region => "section[aria-label], section[aria-labelledby]",
+            Self::Row => "tr",
+            Self::Rowheader => "th[scope=row]",
+            Self::Searchbox => "input[type=search]",
+            Self::Separator => "hr",
+            Self::Slider => "input[type=range]",
+            Self::Spinbutton => "input[type=number]",
+            Self::Status => "output",
+            Self::Table => "table",
+            Self::Textbox => {
+                "input:not([type]), input[type=text], input[type=email], input[type=tel], input[type=url], textarea"
+            }
+            Self::Alert
+            | Self::Grid
+            | Self::Menu
+            | Self::Menuitem
+            | Self::Switch
+            | Self::Tab
+            | Self::Tabpanel
+            | Self::Tooltip => return None
+        };
+        Some(css)
+    }
+}
+
+impl fmt::Display for AriaRole {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result { f.write_str(self.as_str()) }
+}
+
+/// Text to match, either a string or a JavaScript regular expression
+#[derive(Debug, Clone, PartialEq)]
+pub enum TextMatch {
+    Text(String),
+    Regex { source: String, flags: String }
+}
+
+impl TextMatch {
+    pub fn regex(source: &str, flags: &str) -> Self {
+        Self::Regex {
+            source: source.into(),
+            flags: flags.into()
+        }
+    }
+}
+
+impl From<&str> for TextMatch {
+    fn from(s: &str) -> Self { Self::Text(s.into()) }
+}
+
+impl From<String> for TextMatch {
+    fn from(s: String) -> Self { Self::Text(s) }
+}
+
+/// Finds elements by ARIA role, accessible name and state.
+///
+/// The accessible name is matched against `aria-label`, `alt` and the text content of the element,
+/// by case-insensitive substring unless [`exact`](GetByRole::exact) is set.
+pub struct GetByRole<'a> {
+    root: Scope,
+    role: AriaRole,
+    args: RoleArgs<'a>
+}
+
+#[derive(Default)]
+struct RoleArgs<'a> {
+    name: Option<&'a str>,
+    exact: Option<bool>,
+    checked: Option<bool>,
+    pressed: Option<bool>,
+    expanded: Option<bool>
+}
+
+impl<'a> GetByRole<'a> {
+    pub(crate) fn new(root: Scope, role: AriaRole) -> Self {
+        Self {
+            root,
+            role,
+            args: RoleArgs::default()
+        }
+    }
+
+    pub fn selector(&self) -> String {
+        let Self { role, args, .. } = self;
+        let mut s = match role.implicit() {
+            Some(css) => format!(
+                ":is([role={}], :is({}):not([role]))",
+                css_string(role.as_str()),
+                css
+            ),
+            None => format!("[role={}]", css_string(role.as_str()))
+        };
+        if let Some(name) = args.name {
+            let name = css_string(name);
+            if args.exact.unwrap_or_default() {
+                s += &format!(":is([aria-label={0}], [alt={0}], :text-is({0}))", name);
+            } else {
+                s += &format!(
+                    ":is([aria-label*={0} i], [alt*={0} i], :has-text({0}))",
+                    name
+                );
+            }
+        }
+        match args.checked {
+            Some(true) => s += r#":is(:checked, [aria-checked="true"])"#,
+            Some(false) => s += r#":not(:checked):not([aria-checked="true"])"#,
+            None => {}
+        }
+        s += &state("aria-pressed", args.pressed);
+        s += &state("aria-expanded", args.expanded);
+        s
+    }
+
+    pub fn locator(&self) -> Locator { Locator::new(self.root.clone(), &self.selector()) }
+
+    setter! {
+        /// Accessible name
+        name: Option<&'a str>,
+        /// Whether `name` matches the whole name case-sensitively. Defaults to substring match.
+        exact: Option<bool>,
+        /// `checked` or `aria-checked="true"`
+        checked: Option<bool>,
+        /// `aria-pressed="true"`
+        pressed: Option<bool>,
+        /// `aria-expanded="true"`
+        expanded: Option<bool>
+    }
+}
+
+impl fmt::Display for GetByRole<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result { f.write_str(&self.selector()) }
+}
+
+fn state(attr: &str, x: Option<bool>) -> String {
+    match x {
+        Some(true) => format!("[{}=\"true\"]", attr),
+        Some(false) => format!(":not([{}=\"true\"])", attr),
+        None => String::new()
+    }
+}
+
+#[derive(Debug, Clone)]
+enum Kind {
+    Text(TextMatch),
+    Label(String),
+    Placeholder(String),
+    AltText(String),
+    Title(String),
+    TestId { attribute: String, id: String }
+}
+
+/// Finds elements by text, label, placeholder, alt text, title or test id.
+///
+/// Matching is by case-insensitive substring unless [`exact`](GetBy::exact) is set.
+/// Test ids always match exactly.
+pub struct GetBy {
+    root: Scope,
+    kind: Kind,
+    args: GetByArgs
+}
+
+#[derive(Default)]
+struct GetByArgs {
+    exact: Option<bool>
+}
+
+impl GetBy {
+    pub(crate) fn text(root: Scope, text: TextMatch) -> Self { Self::new(root, Kind::Text(text)) }
+
+    pub(crate) fn label(root: Scope, text: &str) -> Self {
+        Self::new(root, Kind::Label(text.into()))
+    }
+
+    pub(crate) fn placeholder(root: Scope, text: &str) -> Self {
+        Self::new(root, Kind::Placeholder(text.into()))
+    }
+
+    pub(crate) fn alt_text(root: Scope, text: &str) -> Self {
+        Self::new(root, Kind::AltText(text.into()))
+    }
+
+    pub(crate) fn title(root: Scope, text: &str) -> Self {
+        Self::new(root, Kind::Title(text.into()))
+    }
+
+    pub(crate) fn test_id(root: Scope, id: &str) -> Self {
+        let attribute = test_id_attribute(&root);
+        Self::new(
+            root,
+            Kind::TestId {
+                attribute,
+                id: id.into()
+            }
+        )
+    }
+
+    fn new(root: Scope, kind: Kind) -> Self {
+        Self {
+            root,
+            kind,
+            args: GetByArgs::default()
+        }
+    }
+
+    pub fn selector(&self) -> String {
+        let exact = self.args.exact.unwrap_or_default();
+        match &self.kind {
+            Kind::Text(TextMatch::Text(t)) if exact => format!(":text-is({})", css_string(t)),
+            Kind::Text(TextMatch::Text(t)) => format!(":text({})", css_string(t)),
+            Kind::Text(TextMatch::Regex { source, flags }) if flags.is_empty() => {
+                format!(":text-matches({})", css_string(source))
+            }
+            Kind::Text(TextMatch::Regex { source, flags }) => format!(
+                ":text-matches({}, {})",
+                css_string(source),
+                css_string(flags)
+            ),
+            Kind::Label(t) => label(t, exact),
+            Kind::Placeholder(t) => attribute("placeholder", t, exact),
+            Kind::AltText(t) => attribute("alt", t, exact),
+            Kind::Title(t) => attribute("title", t, exact),
+            Kind::TestId { attribute: a, id } => attribute(a, id, true)
+        }
+    }
+
+    pub fn locator(&self) -> Locator { Locator::new(self.root.clone(), &self.selector()) }
+
+    setter! {
+        /// Whether to match the whole string case-sensitively. Defaults to substring match.
+        exact: Option<bool>
+    }
+}
+
+impl fmt::Display for GetBy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result { f.write_str(&self.selector()) }
+}
+
+pub(crate) fn test_id_attribute(root: &Scope) -> String {
+    let ctx = match root {
+        Scope::Frame(f) => f.upgrade().and_then(|f| f.context().ok()),
+        Scope::Element(e) => e.upgrade().and_then(|e| e.context().ok())
+    };
+    ctx.map(|c| c.lock().unwrap().test_id_attribute().to_owned())
+        .unwrap_or_else(|| "data-testid".into())
+}
+
+fn attribute(name: &str, value: &str, exact: bool) -> String {
+    if exact {
+        format!("[{}={}]", name, css_string(value))
+    } else {
+        format!("[{}*={} i]", name, css_string(value))
+    }
+}
+
+/// Form controls associated by `<label for>`, nested in a `<label>` or with `aria-label`
+fn label(text: &str, exact: bool) -> String {
+    let t = xpath_literal(text);
+    let (by_text, by_aria) = if exact {
+        (
+            format!("normalize-space(.) = {}", t),
+            format!("normalize-space(@aria-label) = {}", t)
+        )
+    } else {
+        let t = xpath_literal(&text.to_lowercase());
+        (
+            format!("contains({}, {})", xpath_lowercase("normalize-space(.)"), t),
+            format!("contains({}, {})", xpath_lowercase("@aria-label"), t)
+        )
+    };
+    format!(
+        "xpath=.//*[@id = //label[{0}]/@for] | .//label[{0}]//*[self::input or self::textarea or self::select] | .//*[{1}]",
+        by_text, by_aria
+    )
+}
+
+/// XPath 1.0 has no `lower-case`, so only ASCII letters are folded
+fn xpath_lowercase(expr: &str) -> String {
+    format!(
+        "translate({}, 'ABCDEFGHIJKLMNOPQRSTUVWXYZ', 'abcdefghijklmnopqrstuvwxyz')",
+        expr
+    )
+}
+
+fn css_string(s: &str) -> String {
+    let mut quoted = String::with_capacity(s.len() + 2);
+    quoted.push('"');
+    for c in s.chars() {
+        match c {
+            '"' | '\\' => {
+                quoted.push('\\');
+                quoted.push(c);
+            }
+            '\n' => quoted.push_str("\\a "),
+            _ => quoted.push(c)
+        }
+    }
+    quoted.push('"');
+    quoted
+}
+
+fn xpath_literal(s: &str) -> String {
+    if !s.contains('"') {
+        format!("\"{}\"", s)
+    } else if !s.contains('\'') {
+        format!("'{}'", s)
+    } else {
+        let parts = s
+            .split('"')
+            .map(|p| format!("\"{}\"", p))
+            .collect::<Vec<_>>()
+            .join(", '\"', ");
+        format!("concat({})", parts)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Weak;
+
+    fn root() -> Scope { Scope::Frame(Weak::new()) }
+
+    #[test]
+    fn role() {
+        let s = GetByRole::new(root(), AriaRole::Checkbox)
+            .name("Remember me")
+            .exact(true)
+            .checked(false)
+            .selector();
+        assert_eq!(
+            s,
+            r#":is([role="checkbox"], :is(input[type=checkbox]):not([role])):is([aria-label="Remember me"], [alt="Remember me"], :text-is("Remember me")):not(:checked):not([aria-checked="true"])"#
+        );
+        assert_eq!(
+            GetByRole::new(root(), AriaRole::Tab)
+                .pressed(true)
+                .selector(),
+            r#"[role="tab"][aria-pressed="true"]"#
+        );
+        assert_eq!(
+            GetByRole::new(root(), AriaRole::Img)
+                .name("logo")
+                .selector(),
+            r#":is([role="img"], :is(img:not([alt=""])):not([role])):is([aria-label*="logo" i], [alt*="logo" i], :has-text("logo"))"#
+        );
+    }
+
+    #[test]
+    fn text() {
+        assert_eq!(
+            GetBy::text(root(), "a\"b".into()).selector(),
+            r#":text("a\"b")"#
+        );
+        assert_eq!(
+            GetBy::text(root(), TextMatch::regex("^a+$", "i")).selector(),
+            r#":text-matches("^a+$", "i")"#
+        );
+        assert_eq!(
+            GetBy::placeholder(root(), "Name").exact(true).selector(),
+            r#"[placeholder="Name"]"#
+        );
+        assert_eq!(
+            GetBy::title(root(), "Close").selector(),
+            r#"[title*="Close" i]"#
+        );
+        assert_eq!(
+            GetBy::test_id(root(), "x").selector(),
+            r#"[data-testid="x"]"#
+        );
+    }
+
+    #[test]
+    fn label_case() {
+        let s = GetBy::label(root(), "E-Mail").selector();
+        assert!(s.contains(
+            "contains(translate(normalize-space(.), 'ABCDEFGHIJKLMNOPQRSTUVWXYZ', \
+             'abcdefghijklmnopqrstuvwxyz'), \"e-mail\")"
+        ));
+        let exact = GetBy::label(root(), "E-Mail").exact(true).selector();
+        assert!(exact.contains("normalize-space(.) = \"E-Mail\"") && !exact.contains("translate"));
+    }
+
+    #[test]
+    fn quote_xpath() {
+        assert_eq!(xpath_literal("a"), "\"a\"");
+        assert_eq!(xpath_literal("a\"b"), "'a\"b'");
+        assert_eq!(xpath_literal("a\"b'c"), "concat(\"a\", '\"', \"b'c\")");
+    }
+}
@@ -1,8 +1,11 @@
-use crate::imp::{
-    core::*,
-    page::{MouseClickArgs, Page as PageImpl},
-    prelude::*,
-    utils::MouseButton
+use crate::{
+    api::KeyInput,
+    imp::{
+        core::*,
+        page::{MouseClickArgs, Page as PageImpl, TouchGestureArgs, TouchPath},
+        prelude::*,
+        utils::{MouseButton, Position}
+    }
 };
 
 /// Keyboard provides an api for managing a virtual keyboard. The high level api is [`method: Keyboard.type`], which takes
@@ -98,14 +101,14 @@ impl Keyboard {
     /// [`method: Keyboard.up`].
     ///
     /// > NOTE: Modifier keys DO influence `keyboard.down`. Holding down `Shift` will type the text in upper case.
-    pub async fn down(&self, key: &str) -> Result<(), Arc<Error>> {
+    pub async fn down(&self, key: impl Into<KeyInput>) -> Result<(), Arc<Error>> {
         let inner = upgrade(&self.inner)?;
-        inner.key_down(key).await
+        inner.key_down(key.into().as_str()).await
     }
 
-    pub async fn up(&self, key: &str) -> Result<(), Arc<Error>> {
+    pub async fn up(&self, key: impl Into<KeyInput>) -> Result<(), Arc<Error>> {
         let inner = upgrade(&self.inner)?;
-        inner.key_up(key).await
+        inner.key_up(key.into().as_str()).await
     }
 
     /// Dispatches only `input` event, does not emit the `keydown`, `keyup` or `keypress` events.
@@ -138,9 +141,13 @@ impl Keyboard {
     }
 
     /// Shortcut for [`method: Keyboard.down`] and [`method: Keyboard.up`].
-    pub async fn press(&self, key: &str, delay: Option<f64>) -> Result<(), Arc<Error>> {
+    pub async fn press(
+        &self,
+        key: impl Into<KeyInput>,
+        delay: Option<f64>
+    ) -> Result<(), Arc<Error>> {
         let inner = upgrade(&self.inner)?;
-        inner.key_press(key, delay).await
+        inner.key_press(key.into().as_str(), delay).await
     }
 }
 
@@ -152,6 +159,25 @@ impl Mouse {
         inner.mouse_move(x, y, steps).await
     }
 
+    /// Like [`move`](Mouse::move) with the option of following a curved path.
+    /// A curve starts from the same tracked position that [`wheel`](Mouse::wheel) uses.
+    pub fn move_builder(&self, x: f64, y: f64) -> MoveBuilder {
+        MoveBuilder::new(self.inner.clone(), x, y)
+    }
+
+    /// Dispatches a `wheel` event at the current mouse position. Unless the event is cancelled,
+    /// the closest scrollable ancestor of the element there, or the page, is scrolled by the deltas.
+    ///
+    /// The position is where [`move`](Mouse::move), [`click_builder`](Mouse::click_builder) or
+    /// [`dblclick_builder`](Mouse::dblclick_builder) last put the mouse, `(0, 0)` before any of them.
+    /// Actions on elements and selectors, like `ElementHandle::click`, also move the mouse
+    /// but are not tracked, so call [`move`](Mouse::move) first to wheel over a specific point.
+    ///
+    /// > NOTE: The event is dispatched from the page, so `isTrusted` is `false`.
+    pub async fn wheel(&self, delta_x: f64, delta_y: f64) -> ArcResult<()> {
+        upgrade(&self.inner)?.mouse_wheel(delta_x, delta_y).await
+    }
+
     pub async fn down(
         &self,
         button: Option<MouseButton>,
@@ -189,27 +215,212 @@ impl TouchScreen {
         let inner = upgrade(&self.inner)?;
         inner.screen_tap(x, y).await
     }
+
+    /// One finger moving from `from` to `to`.
+    pub fn swipe_builder(&self, from: Position, to: Position) -> GestureBuilder {
+        GestureBuilder::new(self.inner.clone()).finger(from, to)
+    }
+
+    /// One finger held still at `at` for 800 milliseconds by default.
+    pub fn long_press_builder(&self, at: Position) -> GestureBuilder {
+        GestureBuilder::new(self.inner.clone())
+            .finger(at, at)
+            .duration(800.0)
+            .steps(0)
+    }
+
+    /// Two fingers on a horizontal line through `center`, moving from `from_distance` to
+    /// `to_distance` apart. Zooms in when `to_distance` is larger.
+    pub fn pinch_builder(
+        &self,
+        center: Position,
+        from_distance: f64,
+        to_distance: f64
+    ) -> GestureBuilder {
+        let at = |d: f64| {
+            let left = Position {
+                x: center.x - d / 2.0,
+                y: center.y
+            };
+            let right = Position {
+                x: center.x + d / 2.0,
+                y: center.y
+            };
+            (left, right)
+        };
+        let (l1, r1) = at(from_distance);
+        let (l2, r2) = at(to_distance);
+        GestureBuilder::new(self.inner.clone())
+            .finger(l1, l2)
+            .finger(r1, r2)
+    }
+
+    /// Two fingers 40 pixels apart moving together from `from` to `to`.
+    pub fn pan_builder(&self, from: Position, to: Position) -> GestureBuilder {
+        let offset = |p: Position, dx: f64| Position {
+            x: p.x + dx,
+            y: p.y
+        };
+        GestureBuilder::new(self.inner.clone())
+            .finger(offset(from, -20.0), offset(to, -20.0))
+            .finger(offset(from, 20.0), offset(to, 20.0))
+    }
+
+    /// A gesture of any fingers added with [`GestureBuilder::finger`].
+    pub fn gesture_builder(&self) -> GestureBuilder { GestureBuilder::new(self.inner.clone()) }
+}
+
+pub struct MoveBuilder {
+    inner: Weak<PageImpl>,
+    x: f64,
+    y: f64,
+    args: MoveArgs,
+    deadline: Option<Duration>
+}
+
+#[derive(Default)]
+struct MoveArgs {
+    steps: Option<i32>,
+    curve: Option<f64>
+}
+
+impl MoveBuilder {
+    pub(crate) fn new(inner: Weak<PageImpl>, x: f64, y: f64) -> Self {
+        Self {
+            inner,
+            x,
+            y,
+            args: MoveArgs::default(),
+            deadline: None
+        }
+    }
+
+    pub async fn r#move(self) -> ArcResult<()> {
+        let Self {
+            inner,
+            x,
+            y,
+            args,
+            deadline
+        } = self;
+        let inner = upgrade(&inner)?;
+        let f = async {
+            let bend = match args.curve {
+                Some(c) if c != 0.0 => c,
+                _ => return inner.mouse_move(x, y, args.steps).await
+            };
+            let steps = args.steps.unwrap_or(1).max(1) as usize;
+            for (x, y) in curve(inner.mouse_position(), (x, y), steps, bend) {
+                inner.mouse_move(x, y, None).await?;
+            }
+            Ok(())
+        };
+        with_deadline(deadline, f).await
+    }
+
+    deadline! {}
+
+    setter! {
+        /// Number of intermediate `mousemove` events. Defaults to 1.
+        steps: Option<i32>,
+        /// Bends the path sideways by this fraction of its length. Negative values bend it the other way.
+        curve: Option<f64>
+    }
+}
+
+/// Synthesizes `touchstart`, `touchmove` and `touchend` events in the main frame. Points are in
+/// CSS pixels of the viewport, like [`ElementHandle::bounding_box`](crate::api::ElementHandle::bounding_box)
+/// and its [`center`](crate::api::FloatRect::center).
+///
+/// > NOTE: The events are dispatched from the page, so `isTrusted` is `false`. The browser context
+/// > needs `has_touch`, or `Touch` may not be constructible.
+pub struct GestureBuilder {
+    inner: Weak<PageImpl>,
+    args: TouchGestureArgs,
+    deadline: Option<Duration>
+}
+
+impl GestureBuilder {
+    pub(crate) fn new(inner: Weak<PageImpl>) -> Self {
+        Self {
+            inner,
+            args: TouchGestureArgs::default(),
+            deadline: None
+        }
+    }
+
+    /// Adds a finger moving in a straight line from `from` to `to`
+    pub fn finger(mut self, from: Position, to: Position) -> Self {
+        self.args.fingers.push(TouchPath { from, to });
+        self
+    }
+
+    pub async fn gesture(self) -> ArcResult<()> {
+        let Self {
+            inner,
+            args,
+            deadline
+        } = self;
+        with_deadline(deadline, upgrade(&inner)?.touch_gesture(args)).await
+    }
+
+    deadline! {}
+
+    setter! {
+        /// Time in milliseconds from `touchstart` to `touchend`. Defaults to 300.
+        duration: Option<f64>,
+        /// Number of `touchmove` events, evenly spaced in time. Defaults to 10.
+        steps: Option<i32>
+    }
+}
+
+/// Points of a quadratic Bézier curve from `from` to `to`, without `from`.
+/// The control point is off the midpoint by `bend` times the distance.
+fn curve(from: (f64, f64), to: (f64, f64), steps: usize, bend: f64) -> Vec<(f64, f64)> {
+    let (dx, dy) = (to.0 - from.0, to.1 - from.1);
+    let control = (from.0 + dx / 2.0 - dy * bend, from.1 + dy / 2.0 + dx * bend);
+    let at = |t: f64, a: f64, c: f64, b: f64| {
+        let u = 1.0 - t;
+        u * u * a + 2.0 * u * t * c + t * t * b
+    };
+    (1..=steps)
+        .map(|i| {
+            let t = i as f64 / steps as f64;
+            (at(t, from.0, control.0, to.0), at(t, from.1, control.1, to.1))
+        })
+        .collect()
 }
 
 macro_rules! clicker {
     ($t: ident, $f: ident, $mf: ident) => {
         pub struct $t {
             inner: Weak<PageImpl>,
-            args: MouseClickArgs
+            args: MouseClickArgs,
+            deadline: Option<Duration>
         }
 
         impl $t {
             pub(crate) fn new(inner: Weak<PageImpl>, x: f64, y: f64) -> Self {
                 let args = MouseClickArgs::new(x, y);
-                Self { inner, args }
+                Self {
+                    inner,
+                    args,
+                    deadline: None
+                }
             }
 
             pub async fn $f(self) -> Result<(), Arc<Error>> {
-                let Self { inner, args } = self;
-                let _ = upgrade(&inner)?.$mf(args).await?;
+                let Self {
+                    inner,
+                    args,
+                    deadline
+                } = self;
+                let _ = with_deadline(deadline, upgrade(&inner)?.$mf(args)).await?;
                 Ok(())
             }
 
+            deadline! {}
+
             setter! {
                 /// Defaults to `left`.
                 button: Option<MouseButton>,
@@ -224,3 +435,16 @@ macro_rules! clicker {
 
 clicker!(ClickBuilder, click, mouse_click);
 clicker!(DblClickBuilder, dblclick, mouse_dblclick);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn curved() {
+        let points = curve((0.0, 0.0), (100.0, 0.0), 4, 0.5);
+        assert_eq!(points.len(), 4);
+        assert_eq!(points[3], (100.0, 0.0));
+        assert_eq!(points[1], (50.0, 25.0));
+    }
+}
@@ -1,4 +1,11 @@
-use crate::imp::{core::*, js_handle::JsHandle as Impl, prelude::*};
+use crate::{
+    api::ElementHandle,
+    imp::{
+        core::*,
+        js_handle::{self, Handle, JsHandle as Impl},
+        prelude::*
+    }
+};
 use std::fmt;
 
 /// JsHandle represents an in-page JavaScript object. JsHandles can be created with the [`method: Page.evaluateHandle`]
@@ -16,32 +23,67 @@ use std::fmt;
 /// JsHandle instances can be used as an argument in [`method: Page.evalOnSelector`], [`method: Page.evaluate`] and
 /// [`method: Page.evaluateHandle`] methods.
 pub struct JsHandle {
-    inner: Weak<Impl>
+    inner: Handle,
+    dispose_on_drop: bool
 }
 
 impl PartialEq for JsHandle {
     fn eq(&self, other: &Self) -> bool {
-        let a = self.inner.upgrade();
-        let b = other.inner.upgrade();
-        a.and_then(|a| b.map(|b| (a, b)))
-            .map(|(a, b)| a.guid() == b.guid())
-            .unwrap_or_default()
+        let a = self.guid().ok();
+        let b = other.guid().ok();
+        a.is_some() && a == b
     }
 }
 
+/// Calls `$e` with the upgraded object bound to `$h`, whichever kind of handle it is
+macro_rules! with_handle {
+    ($s:expr, $h:ident => $e:expr) => {
+        match &$s.inner {
+            Handle::Js(w) => {
+                let $h = upgrade(w)?;
+                $e
+            }
+            Handle::Element(w) => {
+                let $h = upgrade(w)?;
+                $e
+            }
+        }
+    };
+}
+
 impl JsHandle {
-    pub(crate) fn new(inner: Weak<Impl>) -> Self { Self { inner } }
+    pub(crate) fn new(inner: Weak<Impl>) -> Self { Self::from_handle(Handle::Js(inner)) }
+
+    pub(crate) fn from_handle(inner: Handle) -> Self {
+        Self {
+            inner,
+            dispose_on_drop: false
+        }
+    }
+
+    /// Sends `dispose` in the background when this value is dropped.
+    /// Other `JsHandle` values referring to the same object become unusable after that.
+    pub fn dispose_on_drop(mut self, x: bool) -> Self {
+        self.dispose_on_drop = x;
+        self
+    }
 
     pub(crate) fn guid(&self) -> Result<Str<Guid>, Error> {
-        Ok(upgrade(&self.inner)?.guid().to_owned())
+        with_handle!(self, h => Ok(h.guid().to_owned()))
+    }
+
+    /// Returns the element this handle refers to, if it is one.
+    pub fn as_element(&self) -> Option<ElementHandle> {
+        match &self.inner {
+            Handle::Element(w) => Some(ElementHandle::new(w.clone())),
+            Handle::Js(_) => None
+        }
     }
 
     /// Fetches a single property from the referenced object.
-    pub async fn get_property(&mut self, name: &str) -> ArcResult<JsHandle> {
-        upgrade(&self.inner)?
-            .get_property(name)
-            .await
-            .map(JsHandle::new)
+    pub async fn get_property(&self, name: &str) -> ArcResult<JsHandle> {
+        let h = with_handle!(self, h => js_handle::get_property(&*h, name).await?);
+        Ok(JsHandle::from_handle(h))
     }
 
     /// The method returns a map with **own property names** as keys and JsHandle instances for the property values.
@@ -53,33 +95,85 @@ impl JsHandle {
     /// const documentHandle = properties.get('document');
     /// await handle.dispose();
     /// ```
-    pub async fn get_properties(&mut self) -> ArcResult<HashMap<String, JsHandle>> {
-        let m = upgrade(&self.inner)?.get_properties().await?;
-        Ok(m.into_iter().map(|(k, v)| (k, JsHandle::new(v))).collect())
+    pub async fn get_properties(&self) -> ArcResult<HashMap<String, JsHandle>> {
+        let m = with_handle!(self, h => js_handle::get_properties(&*h).await?);
+        Ok(m.into_iter().map(|(k, v)| (k, JsHandle::from_handle(v))).collect())
     }
 
-    pub async fn dispose(&mut self) -> ArcResult<()> { upgrade(&self.inner)?.dispose().await }
+    pub async fn dispose(&self) -> ArcResult<()> { with_handle!(self, h => h.dispose().await) }
 
     /// Returns a JSON representation of the object. If the object has a `toJSON` function, it **will not be called**.
     ///
     /// > NOTE: The method will return an empty JSON object if the referenced object is not stringifiable. It will throw an
     /// error if the object has circular references.
-    pub async fn json_value<U>(&mut self) -> ArcResult<U>
+    pub async fn json_value<U>(&self) -> ArcResult<U>
+    where
+        U: DeserializeOwned
+    {
+        with_handle!(self, h => js_handle::json_value(&*h).await)
+    }
+
+    /// Returns the return value of `expression`, called with this handle as the first argument and `arg` as the second.
+    ///
+    /// ```js
+    /// const tweetHandle = await page.$('.tweet .retweets');
+    /// expect(await tweetHandle.evaluate(node => node.innerText)).toBe('10 retweets');
+    /// ```
+    pub async fn evaluate<T, U>(&self, expression: &str, arg: Option<T>) -> ArcResult<U>
     where
+        T: Serialize,
         U: DeserializeOwned
     {
-        upgrade(&self.inner)?.json_value().await
+        with_handle!(self, h => js_handle::evaluate(&*h, expression, arg).await)
+    }
+
+    /// Like [`evaluate`](JsHandle::evaluate) but returns the result as a handle.
+    /// Use [`as_element`](JsHandle::as_element) when it is an element.
+    pub async fn evaluate_handle<T>(&self, expression: &str, arg: Option<T>) -> ArcResult<JsHandle>
+    where
+        T: Serialize
+    {
+        let h = with_handle!(self, h => js_handle::evaluate_handle(&*h, expression, arg).await?);
+        Ok(JsHandle::from_handle(h))
     }
+}
 
-    // evaluate
+impl Drop for JsHandle {
+    fn drop(&mut self) {
+        if !self.dispose_on_drop {
+            return;
+        }
+        match &self.inner {
+            Handle::Js(w) => {
+                if let Some(inner) = w.upgrade() {
+                    spawn_detached(async move {
+                        inner.dispose().await.ok();
+                    });
+                }
+            }
+            Handle::Element(w) => {
+                if let Some(inner) = w.upgrade() {
+                    spawn_detached(async move {
+                        inner.dispose().await.ok();
+                    });
+                }
+            }
+        }
+    }
 }
 
+/// The preview of the driver, such as `JSHandle@object` or `JSHandle@<div id="a"></div>`
 impl fmt::Display for JsHandle {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        if let Some(inner) = self.inner.upgrade() {
-            inner.fmt(f)
-        } else {
-            write!(f, "")
+        match &self.inner {
+            Handle::Js(w) => match w.upgrade() {
+                Some(inner) => inner.fmt(f),
+                None => write!(f, "")
+            },
+            Handle::Element(w) => match w.upgrade() {
+                Some(inner) => inner.fmt(f),
+                None => write!(f, "")
+            }
         }
     }
 }
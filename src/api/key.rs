@@ -0,0 +1,256 @@
+//! Typed key names and shortcuts for [`Keyboard`](crate::api::Keyboard) and `press_builder`s.
+//!
+//! ```ignore
+//! page.keyboard.press(Key::Enter, None).await?;
+//! page.keyboard.press(Shortcut::char('a').with(Key::ControlOrMeta), None).await?;
+//! let s: Shortcut = "Control+Shift+T".parse()?;
+//! ```
+use std::{fmt, str::FromStr};
+
+macro_rules! keys {
+    ($($(#[$meta:meta])* $k:ident),*) => {
+        /// Names of keys in the US keyboard layout, as in
+        /// [KeyboardEvent.code](https://developer.mozilla.org/en-US/docs/Web/API/KeyboardEvent/code),
+        /// and the modifiers without side.
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+        pub enum Key {
+            $($(#[$meta])* $k),*
+        }
+
+        impl Key {
+            pub const ALL: &'static [Key] = &[$(Key::$k),*];
+
+            pub fn as_str(&self) -> &'static str {
+                match self {
+                    $(Key::$k => stringify!($k)),*
+                }
+            }
+        }
+    };
+}
+
+keys! {
+    Escape, F1, F2, F3, F4, F5, F6, F7, F8, F9, F10, F11, F12,
+    Backquote, Digit1, Digit2, Digit3, Digit4, Digit5, Digit6, Digit7, Digit8, Digit9, Digit0,
+    Minus, Equal, Backslash, Backspace,
+    Tab, KeyQ, KeyW, KeyE, KeyR, KeyT, KeyY, KeyU, KeyI, KeyO, KeyP, BracketLeft, BracketRight,
+    CapsLock, KeyA, KeyS, KeyD, KeyF, KeyG, KeyH, KeyJ, KeyK, KeyL, Semicolon, Quote, Enter,
+    ShiftLeft, KeyZ, KeyX, KeyC, KeyV, KeyB, KeyN, KeyM, Comma, Period, Slash, ShiftRight,
+    ControlLeft, MetaLeft, AltLeft, Space, AltRight, AltGraph, MetaRight, ContextMenu,
+    ControlRight,
+    PrintScreen, ScrollLock, Pause,
+    PageUp, PageDown, Insert, Delete, Home, End,
+    ArrowLeft, ArrowUp, ArrowRight, ArrowDown,
+    NumLock, NumpadDivide, NumpadMultiply, NumpadSubtract,
+    Numpad7, Numpad8, Numpad9, Numpad4, Numpad5, Numpad6, NumpadAdd,
+    Numpad1, Numpad2, Numpad3, Numpad0, NumpadDecimal, NumpadEnter,
+    Shift, Control, Alt, Meta,
+    /// `Meta` on macOS and `Control` elsewhere
+    ControlOrMeta
+}
+
+impl Key {
+    pub fn is_modifier(&self) -> bool {
+        matches!(
+            self,
+            Key::Shift
+                | Key::ShiftLeft
+                | Key::ShiftRight
+                | Key::Control
+                | Key::ControlLeft
+                | Key::ControlRight
+                | Key::Alt
+                | Key::AltLeft
+                | Key::AltRight
+                | Key::Meta
+                | Key::MetaLeft
+                | Key::MetaRight
+                | Key::ControlOrMeta
+        )
+    }
+
+    /// The key the driver is sent, with [`ControlOrMeta`](Key::ControlOrMeta) decided by the
+    /// platform this runs on
+    pub fn resolve(self) -> Key {
+        match self {
+            Key::ControlOrMeta if cfg!(target_os = "macos") => Key::Meta,
+            Key::ControlOrMeta => Key::Control,
+            k => k
+        }
+    }
+}
+
+impl fmt::Display for Key {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result { f.write_str(self.as_str()) }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[error("Unknown key {0:?}")]
+pub struct UnknownKey(pub String);
+
+impl FromStr for Key {
+    type Err = UnknownKey;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Key::ALL
+            .iter()
+            .find(|k| k.as_str() == s)
+            .copied()
+            .ok_or_else(|| UnknownKey(s.into()))
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum Last {
+    Key(Key),
+    Char(char)
+}
+
+/// Keys held down while the last one is pressed, like `Control+Shift+T`
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Shortcut {
+    modifiers: Vec<Key>,
+    key: Last
+}
+
+impl Shortcut {
+    pub fn new(key: Key) -> Self {
+        Self {
+            modifiers: Vec::new(),
+            key: Last::Key(key)
+        }
+    }
+
+    /// Presses the key producing `c`. Letters are case-sensitive.
+    pub fn char(c: char) -> Self {
+        Self {
+            modifiers: Vec::new(),
+            key: Last::Char(c)
+        }
+    }
+
+    /// Holds `modifier` down, after the ones added before
+    pub fn with(mut self, modifier: Key) -> Self {
+        self.modifiers.push(modifier);
+        self
+    }
+
+    pub fn modifiers(&self) -> &[Key] { &self.modifiers }
+
+    fn write(&self, f: &mut fmt::Formatter<'_>, resolve: bool) -> fmt::Result {
+        for m in &self.modifiers {
+            let m = if resolve { m.resolve() } else { *m };
+            write!(f, "{}+", m)?;
+        }
+        match self.key {
+            Last::Key(k) if resolve => write!(f, "{}", k.resolve()),
+            Last::Key(k) => write!(f, "{}", k),
+            Last::Char(c) => write!(f, "{}", c)
+        }
+    }
+}
+
+impl From<Key> for Shortcut {
+    fn from(k: Key) -> Self { Self::new(k) }
+}
+
+impl fmt::Display for Shortcut {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result { self.write(f, false) }
+}
+
+/// Every part before the last must be a [`Key`]. The last is a key or a single character.
+impl FromStr for Shortcut {
+    type Err = UnknownKey;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (modifiers, key) = if s == "+" {
+            ("", "+")
+        } else if let Some(m) = s.strip_suffix("++") {
+            (m, "+")
+        } else {
+            match s.rfind('+') {
+                Some(i) => (&s[..i], &s[i + 1..]),
+                None => ("", s)
+            }
+        };
+        let modifiers = if modifiers.is_empty() {
+            Vec::new()
+        } else {
+            modifiers.split('+').map(str::parse).collect::<Result<_, _>>()?
+        };
+        let mut cs = key.chars();
+        let key = match (cs.next(), cs.next()) {
+            (Some(c), None) => Last::Char(c),
+            _ => Last::Key(key.parse()?)
+        };
+        Ok(Self { modifiers, key })
+    }
+}
+
+/// What [`Keyboard`](crate::api::Keyboard) and `press_builder`s take: a [`Key`], a [`Shortcut`],
+/// a character or a string. Strings that parse as a [`Shortcut`] get `ControlOrMeta` resolved,
+/// others are sent as they are.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KeyInput(String);
+
+impl KeyInput {
+    pub fn as_str(&self) -> &str { &self.0 }
+
+    pub(crate) fn into_string(self) -> String { self.0 }
+}
+
+impl From<Key> for KeyInput {
+    fn from(k: Key) -> Self { Self(k.resolve().as_str().into()) }
+}
+
+impl From<Shortcut> for KeyInput {
+    fn from(s: Shortcut) -> Self { Self::from(&s) }
+}
+
+impl From<&Shortcut> for KeyInput {
+    fn from(s: &Shortcut) -> Self {
+        struct Resolved<'a>(&'a Shortcut);
+        impl fmt::Display for Resolved<'_> {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result { self.0.write(f, true) }
+        }
+        Self(Resolved(s).to_string())
+    }
+}
+
+impl From<char> for KeyInput {
+    fn from(c: char) -> Self { Self(c.to_string()) }
+}
+
+impl From<&str> for KeyInput {
+    fn from(s: &str) -> Self {
+        match s.parse::<Shortcut>() {
+            Ok(shortcut) => shortcut.into(),
+            Err(_) => Self(s.into())
+        }
+    }
+}
+
+impl From<String> for KeyInput {
+    fn from(s: String) -> Self { Self::from(s.as_str()) }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn shortcut() {
+        let s: Shortcut = "Control+Shift+T".parse().unwrap();
+        assert_eq!(s, Shortcut::char('T').with(Key::Control).with(Key::Shift));
+        assert_eq!(s.to_string(), "Control+Shift+T");
+        assert_eq!("Control++".parse::<Shortcut>().unwrap().to_string(), "Control++");
+        assert_eq!("+".parse(), Ok(Shortcut::char('+')));
+        assert_eq!("ArrowUp".parse(), Ok(Shortcut::new(Key::ArrowUp)));
+        assert_eq!("Contol+A".parse::<Shortcut>(), Err(UnknownKey("Contol".into())));
+        assert!("Control+Entr".parse::<Shortcut>().is_err());
+        let meta = if cfg!(target_os = "macos") { "Meta" } else { "Control" };
+        assert_eq!(KeyInput::from("ControlOrMeta+a").as_str(), format!("{}+a", meta));
+        assert_eq!(KeyInput::from("Contol+A").as_str(), "Contol+A");
+        assert!(Key::ALL.iter().all(|k| k.as_str().parse() == Ok(*k)));
+    }
+}
@@ -0,0 +1,549 @@
+use crate::{
+    api::{expect::is_transient, ElementHandle},
+    imp::{
+        core::*,
+        element_handle::{ClickArgs, ElementHandle as ElementHandleImpl, FillArgs},
+        frame::Frame as FrameImpl,
+        prelude::*,
+        utils::{KeyboardModifier, MouseButton, Position}
+    }
+};
+use std::{fmt, time::Instant};
+
+/// Locator represents a way to find element(s) on the page at any moment.
+/// Unlike [`ElementHandle`], it is resolved again on every action, so it doesn't go stale when the DOM re-renders.
+///
+/// Actions on a single element are strict: they fail with [`Error::StrictModeViolation`](crate::Error::StrictModeViolation)
+/// when more than one element matches, and wait until an element is attached otherwise.
+/// Clicks and fills resolve the element again when it is detached before the action completes,
+/// all within one `timeout`.
+///
+/// ```js
+/// const locator = page.locator('text=Submit');
+/// await locator.click();
+/// ```
+#[derive(Debug, Clone)]
+pub struct Locator {
+    root: Scope,
+    steps: Vec<Step>
+}
+
+#[derive(Debug, Clone)]
+pub(crate) enum Scope {
+    Frame(Weak<FrameImpl>),
+    Element(Weak<ElementHandleImpl>)
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Step {
+    Selector(String),
+    Nth(i32),
+    /// Into the document of the single matching iframe
+    ContentFrame
+}
+
+/// FrameLocator represents a view to an `iframe` on the page.
+/// Like [`Locator`], the iframe is found again on every action, so it keeps working after the iframe reloads.
+/// Actions fail with [`Error::NotIframe`](crate::Error::NotIframe) when the matched element is not an iframe.
+///
+/// ```js
+/// const button = page.frameLocator('#payment').locator('text=Pay');
+/// await button.click();
+/// ```
+#[derive(Debug, Clone)]
+pub struct FrameLocator {
+    /// Matches the iframe element
+    iframe: Locator
+}
+
+/// Used when the frame is not attached to a page
+const DEFAULT_TIMEOUT: f64 = 30000.;
+const POLLING: Duration = Duration::from_millis(100);
+
+macro_rules! is_checked {
+    ($f: ident) => {
+        pub async fn $f(&self) -> ArcResult<bool> {
+            let e = self.resolve_one(None).await?;
+            e.$f().await
+        }
+    };
+}
+
+impl Locator {
+    pub(crate) fn new(root: Scope, selector: &str) -> Self {
+        Self {
+            root,
+            steps: vec![Step::Selector(selector.into())]
+        }
+    }
+
+    pub(crate) fn in_frame(frame: Weak<FrameImpl>, selector: &str) -> Self {
+        Self::new(Scope::Frame(frame), selector)
+    }
+
+    pub(crate) fn in_element(element: Weak<ElementHandleImpl>, selector: &str) -> Self {
+        Self::new(Scope::Element(element), selector)
+    }
+
+    /// Finds elements matching `selector` inside the elements of this locator.
+    pub fn locator(&self, selector: &str) -> Locator {
+        let mut steps = self.steps.clone();
+        match steps.last_mut() {
+            Some(Step::Selector(s)) => *s = format!("{} >> {}", s, selector),
+            _ => steps.push(Step::Selector(selector.into()))
+        }
+        Self {
+            root: self.root.clone(),
+            steps
+        }
+    }
+
+    /// Enters the iframe matching `selector` inside the elements of this locator.
+    pub fn frame_locator(&self, selector: &str) -> FrameLocator {
+        FrameLocator::new(self.locator(selector))
+    }
+
+    /// Returns locator to the n-th matching element. 0-based, and negative values count from the end.
+    pub fn nth(&self, index: i32) -> Locator {
+        let mut steps = self.steps.clone();
+        steps.push(Step::Nth(index));
+        Self {
+            root: self.root.clone(),
+            steps
+        }
+    }
+
+    pub fn first(&self) -> Locator { self.nth(0) }
+
+    pub fn last(&self) -> Locator { self.nth(-1) }
+
+    /// Returns the number of elements matching the locator.
+    pub async fn count(&self) -> ArcResult<usize> {
+        let es = self.resolve().await?;
+        let n = es.len();
+        release(es);
+        Ok(n)
+    }
+
+    /// Returns a locator for each element currently matching.
+    pub async fn all(&self) -> ArcResult<Vec<Locator>> {
+        let n = self.count().await?;
+        Ok((0..n as i32).map(|i| self.nth(i)).collect())
+    }
+
+    /// Resolves to the single matching element, waiting for it up to the default timeout.
+    pub async fn element_handle(&self) -> ArcResult<ElementHandle> {
+        let e = self.resolve_one(None).await?.keep();
+        Ok(ElementHandle::new(Arc::downgrade(&e)))
+    }
+
+    /// Resolves to all elements currently matching. Doesn't wait.
+    pub async fn element_handles(&self) -> ArcResult<Vec<ElementHandle>> {
+        let es = self.resolve().await?;
+        Ok(es.into_iter().map(ElementHandle::new).collect())
+    }
+
+    pub async fn text_content(&self) -> ArcResult<Option<String>> {
+        self.resolve_one(None).await?.text_content().await
+    }
+
+    pub async fn inner_text(&self) -> ArcResult<String> {
+        self.resolve_one(None).await?.inner_text().await
+    }
+
+    pub async fn inner_html(&self) -> ArcResult<String> {
+        self.resolve_one(None).await?.inner_html().await
+    }
+
+    pub async fn get_attribute(&self, name: &str) -> ArcResult<Option<String>> {
+        self.resolve_one(None).await?.get_attribute(name).await
+    }
+
+    is_checked! {is_checked}
+    is_checked! {is_disabled}
+    is_checked! {is_editable}
+    is_checked! {is_enabled}
+
+    /// Doesn't wait. Returns `false` if no element matches.
+    pub async fn is_visible(&self) -> ArcResult<bool> {
+        match self.resolve_now().await? {
+            Some(e) => e.is_visible().await,
+            None => Ok(false)
+        }
+    }
+
+    /// Doesn't wait. Returns `true` if no element matches.
+    pub async fn is_hidden(&self) -> ArcResult<bool> {
+        match self.resolve_now().await? {
+            Some(e) => e.is_hidden().await,
+            None => Ok(true)
+        }
+    }
+
+    pub fn click_builder(&self) -> ClickBuilder { ClickBuilder::new(self.clone()) }
+
+    pub fn dblclick_builder(&self) -> DblClickBuilder { DblClickBuilder::new(self.clone()) }
+
+    pub fn fill_builder<'a>(&self, value: &'a str) -> FillBuilder<'a> {
+        FillBuilder::new(self.clone(), value)
+    }
+
+    async fn resolve(&self) -> ArcResult<Vec<Weak<ElementHandleImpl>>> {
+        let mut current = vec![self.root.clone()];
+        // The root belongs to the caller
+        let mut owned = false;
+        for step in &self.steps {
+            current = match step {
+                Step::Selector(s) => {
+                    let mut next = Vec::new();
+                    for scope in &current {
+                        next.extend(scope.query_selector_all(s).await?);
+                    }
+                    if owned {
+                        release_scopes(current);
+                    }
+                    next.into_iter().map(Scope::Element).collect()
+                }
+                Step::Nth(i) => {
+                    let n = current.len() as i32;
+                    let index = if *i < 0 { n + i } else { *i };
+                    let picked = if (0..n).contains(&index) {
+                        vec![current.swap_remove(index as usize)]
+                    } else {
+                        Vec::new()
+                    };
+                    if owned {
+                        release_scopes(current);
+                    }
+                    picked
+                }
+                Step::ContentFrame => {
+                    if current.len() > 1 {
+                        let n = current.len();
+                        if owned {
+                            release_scopes(current);
+                        }
+                        return Err(Arc::new(Error::StrictModeViolation(self.to_string(), n)));
+                    }
+                    let mut frames = Vec::new();
+                    for scope in &current {
+                        if let Scope::Element(e) = scope {
+                            frames.push(upgrade(e)?.content_frame().await?);
+                        }
+                    }
+                    if owned {
+                        release_scopes(current);
+                    }
+                    match frames.into_iter().collect::<Option<Vec<_>>>() {
+                        Some(fs) => fs.into_iter().map(Scope::Frame).collect(),
+                        None => return Err(Arc::new(Error::NotIframe(self.to_string())))
+                    }
+                }
+            };
+            owned = true;
+        }
+        Ok(current
+            .into_iter()
+            .filter_map(|s| match s {
+                Scope::Element(e) => Some(e),
+                Scope::Frame(_) => None
+            })
+            .collect())
+    }
+
+    /// At most one element without waiting
+    pub(crate) async fn resolve_now(&self) -> ArcResult<Option<Resolved>> {
+        let mut es = self.resolve().await?;
+        match es.len() {
+            0 => Ok(None),
+            1 => Ok(Some(Resolved(Some(upgrade(&es.remove(0))?)))),
+            n => {
+                release(es);
+                Err(Arc::new(Error::StrictModeViolation(self.to_string(), n)))
+            }
+        }
+    }
+
+    /// Waits for exactly one element. `timeout` is in milliseconds and 0 disables it. Defaults to
+    /// the page's default timeout.
+    pub(crate) async fn resolve_one(&self, timeout: Option<f64>) -> ArcResult<Resolved> {
+        let timeout = match timeout {
+            Some(t) => t,
+            None => self.root.default_timeout().await?
+        };
+        let started = Instant::now();
+        loop {
+            if let Some(e) = self.resolve_now().await? {
+                return Ok(e);
+            }
+            if timeout > 0. && started.elapsed() >= Duration::from_millis(timeout as u64) {
+                return Err(Arc::new(Error::Timeout));
+            }
+            sleep(POLLING).await;
+        }
+    }
+
+    /// Resolves the element and runs `action` on it with the time left of `timeout`.
+    /// When the element is detached in between, like by a re-render, it is resolved again.
+    pub(crate) async fn act<F, Fut, T>(&self, timeout: Option<f64>, action: F) -> ArcResult<T>
+    where
+        F: Fn(Resolved, Option<f64>) -> Fut,
+        Fut: Future<Output = ArcResult<T>>
+    {
+        let timeout = match timeout {
+            Some(t) => t,
+            None => self.root.default_timeout().await?
+        };
+        let started = Instant::now();
+        // 0 disables the timeout
+        let remaining = || {
+            if timeout <= 0. {
+                return Ok(0.);
+            }
+            let left = timeout - started.elapsed().as_millis() as f64;
+            if left > 0. {
+                Ok(left)
+            } else {
+                Err(Arc::new(Error::Timeout))
+            }
+        };
+        loop {
+            let e = self.resolve_one(Some(remaining()?)).await?;
+            match action(e, Some(remaining()?)).await {
+                Err(err) if is_transient(&err) => continue,
+                result => return result
+            }
+        }
+    }
+}
+
+/// An element resolved for a single action. Disposed when dropped.
+pub(crate) struct Resolved(Option<Arc<ElementHandleImpl>>);
+
+impl Resolved {
+    pub(crate) fn keep(mut self) -> Arc<ElementHandleImpl> { self.0.take().unwrap() }
+}
+
+impl std::ops::Deref for Resolved {
+    type Target = ElementHandleImpl;
+
+    fn deref(&self) -> &ElementHandleImpl { self.0.as_ref().unwrap() }
+}
+
+impl Drop for Resolved {
+    fn drop(&mut self) {
+        if let Some(e) = self.0.take() {
+            release(vec![Arc::downgrade(&e)]);
+        }
+    }
+}
+
+impl fmt::Display for Locator {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, step) in self.steps.iter().enumerate() {
+            if i > 0 {
+                write!(f, " >> ")?;
+            }
+            match step {
+                Step::Selector(s) => write!(f, "{}", s)?,
+                Step::Nth(n) => write!(f, "nth={}", n)?,
+                Step::ContentFrame => write!(f, "control=enter-frame")?
+            }
+        }
+        Ok(())
+    }
+}
+
+impl FrameLocator {
+    pub(crate) fn new(iframe: Locator) -> Self { Self { iframe } }
+
+    fn enter(&self) -> Locator {
+        let mut steps = self.iframe.steps.clone();
+        steps.push(Step::ContentFrame);
+        Locator {
+            root: self.iframe.root.clone(),
+            steps
+        }
+    }
+
+    /// Finds elements matching `selector` inside the iframe.
+    pub fn locator(&self, selector: &str) -> Locator {
+        let mut l = self.enter();
+        l.steps.push(Step::Selector(selector.into()));
+        l
+    }
+
+    /// Enters a nested iframe matching `selector` inside the iframe.
+    pub fn frame_locator(&self, selector: &str) -> FrameLocator {
+        FrameLocator::new(self.locator(selector))
+    }
+
+    /// Uses the first matching iframe instead of requiring exactly one.
+    pub fn first(&self) -> FrameLocator { self.nth(0) }
+
+    pub fn last(&self) -> FrameLocator { self.nth(-1) }
+
+    /// Uses the n-th matching iframe. 0-based, and negative values count from the end.
+    pub fn nth(&self, index: i32) -> FrameLocator { FrameLocator::new(self.iframe.nth(index)) }
+}
+
+impl fmt::Display for FrameLocator {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} >> control=enter-frame", self.iframe)
+    }
+}
+
+impl Scope {
+    async fn query_selector_all(&self, selector: &str) -> ArcResult<Vec<Weak<ElementHandleImpl>>> {
+        match self {
+            Scope::Frame(f) => upgrade(f)?.query_selector_all(selector).await,
+            Scope::Element(e) => upgrade(e)?.query_selector_all(selector).await
+        }
+    }
+
+    /// The default timeout of the page, as set by `Page::set_default_timeout` or
+    /// `BrowserContext::set_default_timeout`
+    async fn default_timeout(&self) -> ArcResult<f64> {
+        let frame = match self {
+            Scope::Frame(f) => upgrade(f)?,
+            Scope::Element(e) => {
+                let f = upgrade(e)?.owner_frame().await?;
+                upgrade(&f.ok_or(Error::ObjectNotFound)?)?
+            }
+        };
+        let timeout = frame
+            .page()
+            .and_then(|p| p.upgrade())
+            .map_or(DEFAULT_TIMEOUT, |p| p.default_timeout() as f64);
+        Ok(timeout)
+    }
+}
+
+/// Disposes handles created while resolving
+fn release(es: Vec<Weak<ElementHandleImpl>>) {
+    for e in es.into_iter().filter_map(|e| e.upgrade()) {
+        spawn_detached(async move {
+            e.dispose().await.ok();
+        });
+    }
+}
+
+fn release_scopes(scopes: Vec<Scope>) {
+    release(
+        scopes
+            .into_iter()
+            .filter_map(|s| match s {
+                Scope::Element(e) => Some(e),
+                Scope::Frame(_) => None
+            })
+            .collect()
+    )
+}
+
+macro_rules! clicker {
+    ($t: ident, $f: ident) => {
+        pub struct $t {
+            locator: Locator,
+            args: ClickArgs,
+            deadline: Option<Duration>
+        }
+
+        impl $t {
+            fn new(locator: Locator) -> Self {
+                Self {
+                    locator,
+                    args: ClickArgs::default(),
+                    deadline: None
+                }
+            }
+
+            pub async fn $f(self) -> ArcResult<()> {
+                let Self {
+                    locator,
+                    args,
+                    deadline
+                } = self;
+                let f = locator.act(args.timeout, |e, timeout| {
+                    let mut args = args.clone();
+                    args.timeout = timeout;
+                    async move { e.$f(args).await }
+                });
+                with_deadline(deadline, f).await
+            }
+
+            deadline! {}
+
+            setter! {
+                /// Defaults to `left`.
+                button: Option<MouseButton>,
+                /// defaults to 1. See [UIEvent.detail].
+                click_count: Option<i32>,
+                /// Time to wait between `mousedown` and `mouseup` in milliseconds. Defaults to 0.
+                delay: Option<f64>,
+                /// Whether to bypass the [actionability](https://playwright.dev/docs/actionability/) checks. Defaults to `false`.
+                force: Option<bool>,
+                /// Modifier keys to press. Ensures that only these modifiers are pressed during the operation, and then restores current
+                /// modifiers back. If not specified, currently pressed modifiers are used.
+                modifiers: Option<Vec<KeyboardModifier>>,
+                /// Actions that initiate navigations are waiting for these navigations to happen and for pages to start loading. You can
+                /// opt out of waiting via setting this flag. You would only need this option in the exceptional cases such as navigating to
+                /// inaccessible pages. Defaults to `false`.
+                no_wait_after: Option<bool>,
+                /// A point to use relative to the top-left corner of element padding box. If not specified, uses some visible point of the
+                /// element.
+                position: Option<Position>,
+                /// Maximum time in milliseconds, defaults to 30 seconds, pass `0` to disable timeout. The default value can be changed by
+                /// using the [`method: BrowserContext.setDefaultTimeout`] or [`method: Page.setDefaultTimeout`] methods.
+                timeout: Option<f64>,
+                /// When set, this method only performs the [actionability](https://playwright.dev/docs/actionability/) checks and skips the action. Defaults to
+                /// `false`. Useful to wait until the element is ready for the action without performing it.
+                trial: Option<bool>
+            }
+        }
+    };
+}
+
+clicker!(ClickBuilder, click);
+clicker!(DblClickBuilder, dblclick);
+
+pub struct FillBuilder<'a> {
+    locator: Locator,
+    args: FillArgs<'a>,
+    deadline: Option<Duration>
+}
+
+impl<'a> FillBuilder<'a> {
+    fn new(locator: Locator, value: &'a str) -> Self {
+        Self {
+            locator,
+            args: FillArgs::new(value),
+            deadline: None
+        }
+    }
+
+    pub async fn fill(self) -> ArcResult<()> {
+        let Self {
+            locator,
+            args,
+            deadline
+        } = self;
+        let f = locator.act(args.timeout, |e, timeout| {
+            let mut args = args.clone();
+            args.timeout = timeout;
+            async move { e.fill(args).await }
+        });
+        with_deadline(deadline, f).await
+    }
+
+    deadline! {}
+
+    setter! {
+        /// Actions that initiate navigations are waiting for these navigations to happen and for pages to start loading. You can
+        /// opt out of waiting via setting this flag. You would only need this option in the exceptional cases such as navigating to
+        /// inaccessible pages. Defaults to `false`.
+        no_wait_after: Option<bool>,
+        /// Maximum time in milliseconds, defaults to 30 seconds, pass `0` to disable timeout. The default value can be changed by
+        /// using the [`method: BrowserContext.setDefaultTimeout`] or [`method: Page.setDefaultTimeout`] methods.
+        timeout: Option<f64>
+    }
+}
@@ -2,9 +2,9 @@ pub use crate::{
     api::{
         frame::{
             AddScriptTagBuilder, CheckBuilder, ClickBuilder, DblClickBuilder, FillBuilder,
-            GotoBuilder, HoverBuilder, PressBuilder, SelectOptionBuilder, SetContentBuilder,
-            SetInputFilesBuilder, TapBuilder, TypeBuilder, UncheckBuilder, WaitForFunctionBuilder,
-            WaitForSelectorBuilder
+            FrameTree, GotoBuilder, HoverBuilder, PressBuilder, SelectOptionBuilder,
+            SetContentBuilder, SetInputFilesBuilder, TapBuilder, TypeBuilder, UncheckBuilder,
+            WaitForFunctionBuilder, WaitForSelectorBuilder
         },
         Download, JsHandle, Request
     },
@@ -12,8 +12,16 @@ pub use crate::{
 };
 use crate::{
     api::{
-        input_device::*, Accessibility, BrowserContext, ConsoleMessage, ElementHandle, FileChooser,
-        Frame, Keyboard, Response, TouchScreen, Video, WebSocket, Worker
+        capture::CaptureArgs,
+        drag::DragAndDropBuilder,
+        extract::{Extract, Field},
+        form::FillFormReport,
+        get_by::{AriaRole, GetBy, GetByRole, TextMatch},
+        input_device::*,
+        locator::Scope,
+        table::TableBuilder,
+        Accessibility, BrowserContext, ConsoleMessage, ElementHandle, FileChooser, Frame,
+        FrameLocator, KeyInput, Keyboard, Locator, Response, TouchScreen, Video, WebSocket, Worker
     },
     imp::{
         core::*,
@@ -21,8 +29,8 @@ use crate::{
         page::{EmulateMediaArgs, Evt, Page as Impl, PdfArgs, ReloadArgs, ScreenshotArgs},
         prelude::*,
         utils::{
-            ColorScheme, DocumentLoadState, File, FloatRect, Length, PdfMargins, ScreenshotType,
-            Viewport
+            ColorScheme, DocumentLoadState, File, FloatRect, Length, Margin, PaperFormat,
+            ScreenshotType, UrlMatcher, Viewport
         }
     },
     Error
@@ -155,6 +163,11 @@ impl Page {
         upgrade(&self.inner)?.set_default_timeout(timeout).await
     }
 
+    /// As set by `set_default_timeout` or `BrowserContext::set_default_timeout`
+    pub(crate) fn default_timeout(&self) -> Result<u32, Error> {
+        Ok(upgrade(&self.inner)?.default_timeout())
+    }
+
     pub fn viewport_size(&self) -> Result<Option<Viewport>, Error> {
         Ok(upgrade(&self.inner)?.viewport_size())
     }
@@ -173,8 +186,74 @@ impl Page {
         Ok(upgrade(&self.inner)?.video().map(Video::new))
     }
 
-    ///// Returns frame matching the specified criteria. Either `name` or `url` must be specified.
-    // fn frame(&self) -> Option<Frame> { unimplemented!() }
+    /// Returns the first frame whose name is `name`.
+    pub fn frame_by_name(&self, name: &str) -> Result<Option<Frame>, Error> {
+        self.find_frame(&FrameMatcher::Name(name.into()))
+    }
+
+    /// Returns the first frame whose url matches a glob or [`Regex`](regex::Regex).
+    ///
+    /// ```ignore
+    /// let ad = page.frame_by_url("**/ads/*")?;
+    /// ```
+    pub fn frame_by_url<M: Into<UrlMatcher>>(&self, url: M) -> Result<Option<Frame>, Error> {
+        self.find_frame(&FrameMatcher::Url(url.into()))
+    }
+
+    fn find_frame(&self, matcher: &FrameMatcher) -> Result<Option<Frame>, Error> {
+        for f in self.frames()? {
+            if matcher.matches(&f)? {
+                return Ok(Some(f));
+            }
+        }
+        Ok(None)
+    }
+
+    /// Waits until a matching frame is attached or navigated and returns it.
+    /// Resolves immediately if such a frame exists already. Fails with [`Error::Timeout`](crate::Error::Timeout)
+    /// after the default timeout, unless it is 0.
+    pub async fn wait_for_frame(&self, matcher: FrameMatcher) -> ArcResult<Frame> {
+        let inner = upgrade(&self.inner)?;
+        // Subscribe before looking at current frames so no event is missed
+        let mut rx = inner.subscribe_event();
+        // 0 disables the timeout
+        let timeout = match inner.default_timeout() {
+            0 => None,
+            t => Some(Duration::from_millis(t as u64))
+        };
+        if let Some(f) = self.find_frame(&matcher)? {
+            return Ok(f);
+        }
+        let wait = async {
+            loop {
+                match rx.recv().await {
+                    Ok(Evt::FrameAttached(f)) | Ok(Evt::FrameNavigated(f)) => {
+                        let f = Frame::new(f);
+                        if matcher.matches(&f)? {
+                            return Ok(f);
+                        }
+                    }
+                    Ok(_) | Err(broadcast::error::RecvError::Lagged(_)) => {}
+                    Err(e) => return Err(Arc::new(Error::Event(e)))
+                }
+            }
+        };
+        with_deadline(timeout, wait).await
+    }
+
+    /// The main frame and its descendants. `Display` prints them indented for debugging.
+    ///
+    /// ```text
+    /// https://example.com/
+    ///   https://example.com/ad.html (ad)
+    ///   about:blank
+    /// ```
+    pub fn frame_tree(&self) -> Result<FrameTree, Error> { self.main_frame().tree() }
+
+    /// Scrolls the page by the given amounts like `window.scrollBy`.
+    pub async fn scroll_by(&self, delta_x: f64, delta_y: f64) -> ArcResult<()> {
+        upgrade(&self.inner)?.scroll_by(delta_x, delta_y).await
+    }
 
     /// Brings page to front (activates tab).
     pub async fn bring_to_front(&self) -> ArcResult<()> {
@@ -224,12 +303,10 @@ impl Page {
     /// await page.pdf({path: 'page.pdf'});
     /// ```
     /// 
-    /// The `width`, `height`, and `margin` options accept values labeled with units. Unlabeled values are treated as pixels.
-    ///
-    /// A few examples:
-    /// - `page.pdf({width: 100})` - prints with width set to 100 pixels
-    /// - `page.pdf({width: '100px'})` - prints with width set to 100 pixels
-    /// - `page.pdf({width: '10cm'})` - prints with width set to 10 centimeters.
+    /// The `width`, `height`, and `margin` options take a [`Length`]. Numbers are pixels:
+    /// - `Length::from(100.0)` - prints with width set to 100 pixels
+    /// - `"100px".parse()` - prints with width set to 100 pixels
+    /// - `Length::Cm(10.0)` or `"10cm".parse()` - prints with width set to 10 centimeters.
     ///
     /// All possible units are:
     /// - `px` - pixel
@@ -237,7 +314,7 @@ impl Page {
     /// - `cm` - centimeter
     /// - `mm` - millimeter
     ///
-    /// The `format` options are:
+    /// The [`PaperFormat`]s are:
     /// - `Letter`: 8.5in x 11in
     /// - `Legal`: 8.5in x 14in
     /// - `Tabloid`: 11in x 17in
@@ -252,7 +329,7 @@ impl Page {
     ///
     /// > NOTE: `headerTemplate` and `footerTemplate` markup have the following limitations: > 1. Script tags inside templates
     /// are not evaluated. > 2. Page styles are not visible inside templates.
-    pub fn pdf_builder(&self) -> PdfBuilder<'_, '_, '_, '_, '_, '_, '_, '_, '_, '_> {
+    pub fn pdf_builder(&self) -> PdfBuilder {
         PdfBuilder::new(self.inner.clone())
     }
 
@@ -277,6 +354,16 @@ impl Page {
         ScreenshotBuilder::new(self.inner.clone())
     }
 
+    /// Compares a screenshot of the page with the image at `baseline`. See
+    /// [`visual`](crate::api::visual).
+    #[cfg(feature = "visual")]
+    pub fn compare_screenshot_builder<P: Into<PathBuf>>(
+        &self,
+        baseline: P
+    ) -> crate::api::visual::CompareScreenshotBuilder<'_> {
+        crate::api::visual::CompareScreenshotBuilder::page(self, baseline.into())
+    }
+
     /// This method changes the `CSS media type` through the `media` argument, and/or the `'prefers-colors-scheme'` media
     /// feature, using the `colorScheme` argument.
     ///
@@ -344,6 +431,22 @@ impl Page {
     }
 }
 
+/// Which frame [`Page::wait_for_frame`] waits for
+#[derive(Debug, Clone)]
+pub enum FrameMatcher {
+    Name(String),
+    Url(UrlMatcher)
+}
+
+impl FrameMatcher {
+    fn matches(&self, f: &Frame) -> Result<bool, Error> {
+        Ok(match self {
+            Self::Name(n) => f.name()? == *n,
+            Self::Url(u) => u.is_match(&f.url()?)
+        })
+    }
+}
+
 #[derive(Clone)]
 pub enum Event {
     Close,
@@ -500,6 +603,22 @@ impl Page {
         self.main_frame().query_selector(selector).await
     }
 
+    /// Returns a locator that resolves `selector` in the main frame on every action.
+    pub fn locator(&self, selector: &str) -> Locator { self.main_frame().locator(selector) }
+
+    /// Returns a frame locator that enters the iframe matching `selector` in the main frame on every action.
+    ///
+    /// ```js
+    /// await page.frameLocator('iframe#payment').locator('button').click();
+    /// ```
+    pub fn frame_locator(&self, selector: &str) -> FrameLocator {
+        self.main_frame().frame_locator(selector)
+    }
+
+    fn scope(&self) -> Scope { self.main_frame().scope() }
+
+    get_by! {}
+
     pub async fn query_selector_all(&self, selector: &str) -> ArcResult<Vec<ElementHandle>> {
         self.main_frame().query_selector_all(selector).await
     }
@@ -569,6 +688,51 @@ impl Page {
         self.main_frame().evaluate(expression, arg).await
     }
 
+    /// Extracts `T` from the main frame in one round-trip. See [`extract`](crate::api::extract).
+    pub async fn extract<T: Extract>(&self) -> ArcResult<T> { self.main_frame().extract().await }
+
+    /// Extracts data described by `schema` from the main frame in one round-trip.
+    pub async fn extract_with<U>(&self, schema: &Field) -> ArcResult<U>
+    where
+        U: DeserializeOwned
+    {
+        self.main_frame().extract_with(schema).await
+    }
+
+    /// Fills the form matching `form_selector` in the main frame. See [`form`](crate::api::form).
+    pub async fn fill_form<T>(&self, form_selector: &str, value: &T) -> ArcResult<FillFormReport>
+    where
+        T: Serialize
+    {
+        self.main_frame().fill_form(form_selector, value).await
+    }
+
+    /// Current state of the form matching `form_selector` in the main frame.
+    /// See [`Frame::read_form`].
+    pub async fn read_form(&self, form_selector: &str) -> ArcResult<Value> {
+        self.main_frame().read_form(form_selector).await
+    }
+
+    /// Rows of the table matching `selector` in the main frame, keyed by header.
+    /// See [`table`](crate::api::table).
+    pub async fn extract_table(&self, selector: &str) -> ArcResult<Vec<HashMap<String, String>>> {
+        self.main_frame().extract_table(selector).await
+    }
+
+    pub fn extract_table_builder<'a>(&self, selector: &'a str) -> TableBuilder<'a> {
+        self.main_frame().extract_table_builder(selector)
+    }
+
+    /// Drags the element matching `source` onto the element matching `target` in the main frame.
+    /// See [`drag`](crate::api::drag).
+    pub fn drag_and_drop_builder<'a>(
+        &self,
+        source: &'a str,
+        target: &'a str
+    ) -> DragAndDropBuilder<'a> {
+        self.main_frame().drag_and_drop_builder(source, target)
+    }
+
     pub async fn evaluate_on_selector<T, U>(
         &self,
         selector: &str,
@@ -715,7 +879,11 @@ impl Page {
         self.main_frame().type_builder(selector, text)
     }
 
-    pub fn press_builder<'a, 'b>(&self, selector: &'a str, key: &'b str) -> PressBuilder<'a, 'b> {
+    pub fn press_builder<'a>(
+        &self,
+        selector: &'a str,
+        key: impl Into<KeyInput>
+    ) -> PressBuilder<'a, 'static> {
         self.main_frame().press_builder(selector, key)
     }
 
@@ -737,21 +905,32 @@ macro_rules! navigation {
     ($t: ident, $f: ident) => {
         pub struct $t {
             inner: Weak<Impl>,
-            args: ReloadArgs
+            args: ReloadArgs,
+            deadline: Option<Duration>
         }
 
         impl $t {
             pub(crate) fn new(inner: Weak<Impl>) -> Self {
                 let args = ReloadArgs::default();
-                Self { inner, args }
+                Self {
+                    inner,
+                    args,
+                    deadline: None
+                }
             }
 
             pub async fn $f(self) -> ArcResult<Option<Response>> {
-                let Self { inner, args } = self;
-                let r = upgrade(&inner)?.$f(args).await?;
+                let Self {
+                    inner,
+                    args,
+                    deadline
+                } = self;
+                let r = with_deadline(deadline, upgrade(&inner)?.$f(args)).await?;
                 Ok(r.map(Response::new))
             }
 
+            deadline! {}
+
             setter! {
                 timeout: Option<f64>,
                 /// When to consider operation succeeded, defaults to `load`. Events can be either:
@@ -768,75 +947,148 @@ navigation!(ReloadBuilder, reload);
 navigation!(GoBackBuilder, go_back);
 navigation!(GoForwardBuilder, go_forward);
 
-pub struct PdfBuilder<'a, 'b, 'c, 'd, 'e, 'f, 'g, 'h, 'i, 'j> {
+/// Options of [`Page::pdf_builder`]. Only supported in Chromium headless.
+///
+/// ```ignore
+/// let pdf: Vec<u8> = page
+///     .pdf_builder()
+///     .format(PaperFormat::A4)
+///     .margin(Margin::all(Length::Cm(1.5)))
+///     .footer_template(r#"<span class="pageNumber"></span>"#)
+///     .page_ranges("1-3")
+///     .pdf()
+///     .await?;
+/// ```
+pub struct PdfBuilder {
     inner: Weak<Impl>,
-    args: PdfArgs<'a, 'b, 'c, 'd, 'e, 'f, 'g, 'h, 'i, 'j>
+    args: PdfArgs,
+    deadline: Option<Duration>
 }
 
-impl<'a, 'b, 'c, 'd, 'e, 'f, 'g, 'h, 'i, 'j> PdfBuilder<'a, 'b, 'c, 'd, 'e, 'f, 'g, 'h, 'i, 'j> {
+impl PdfBuilder {
     pub(crate) fn new(inner: Weak<Impl>) -> Self {
         let args = PdfArgs::default();
-        Self { inner, args }
+        Self {
+            inner,
+            args,
+            deadline: None
+        }
     }
 
-    pub async fn pdf(self) -> Result<(), Arc<Error>> {
-        let Self { inner, args } = self;
-        let _ = upgrade(&inner)?.pdf(args).await?;
-        Ok(())
+    /// Returns the PDF. Fails with [`Error::PdfOption`] when `page_ranges` is malformed.
+    pub async fn pdf(self) -> ArcResult<Vec<u8>> {
+        let Self {
+            inner,
+            args,
+            deadline
+        } = self;
+        with_deadline(deadline, upgrade(&inner)?.pdf(args)).await
     }
 
+    /// HTML template for the print header. Should be valid HTML markup with following classes used to inject printing values
+    /// into them:
+    /// - `'date'` formatted print date
+    /// - `'title'` document title
+    /// - `'url'` document location
+    /// - `'pageNumber'` current page number
+    /// - `'totalPages'` total pages in the document
+    ///
+    /// Setting a template turns on `display_header_footer` unless it was set.
+    pub fn header_template(mut self, x: &str) -> Self {
+        self.args.header_template = Some(x.into());
+        self.args.display_header_footer.get_or_insert(true);
+        self
+    }
+
+    pub fn clear_header_template(mut self) -> Self {
+        self.args.header_template = None;
+        self
+    }
+
+    /// HTML template for the print footer. Should use the same format as the `header_template`.
+    pub fn footer_template(mut self, x: &str) -> Self {
+        self.args.footer_template = Some(x.into());
+        self.args.display_header_footer.get_or_insert(true);
+        self
+    }
+
+    pub fn clear_footer_template(mut self) -> Self {
+        self.args.footer_template = None;
+        self
+    }
+
+    /// Paper ranges to print, e.g., `1-5, 8, 11-13`. Either end of a range may be left open, like
+    /// `11-`. Defaults to the empty string, which means print all pages.
+    pub fn page_ranges(mut self, x: &str) -> Self {
+        self.args.page_ranges = Some(x.into());
+        self
+    }
+
+    pub fn clear_page_ranges(mut self) -> Self {
+        self.args.page_ranges = None;
+        self
+    }
+
+    deadline! {}
+
     setter! {
         /// Scale of the webpage rendering. Defaults to `1`. Scale amount must be between 0.1 and 2.
         scale: Option<f64>,
         /// Display header and footer. Defaults to `false`.
         display_header_footer: Option<bool>,
-        /// HTML template for the print header. Should be valid HTML markup with following classes used to inject printing values
-        /// into them:
-        /// - `'date'` formatted print date
-        /// - `'title'` document title
-        /// - `'url'` document location
-        /// - `'pageNumber'` current page number
-        /// - `'totalPages'` total pages in the document
-        header_template: Option<&'a str>,
-        /// HTML template for the print footer. Should use the same format as the `headerTemplate`.
-        footer_template: Option<&'b str>,
         /// Print background graphics. Defaults to `false`.
         print_background: Option<bool>,
         /// Paper orientation. Defaults to `false`.
         landscape: Option<bool>,
-        /// Paper ranges to print, e.g., '1-5, 8, 11-13'. Defaults to the empty string, which means print all pages.
-        page_ranges: Option<&'c str>,
-        /// Paper format. If set, takes priority over `width` or `height` options. Defaults to 'Letter'.
-        format: Option<&'d str>,
-        /// Paper width, accepts values labeled with units.
-        width: Option<Length<'e>>,
-        /// Paper height, accepts values labeled with units.
-        height: Option<Length<'f>>,
+        /// Paper format. If set, takes priority over `width` or `height` options. Defaults to `Letter`.
+        format: Option<PaperFormat>,
+        /// Paper width
+        width: Option<Length>,
+        /// Paper height
+        height: Option<Length>,
         /// Give any CSS `@page` size declared in the page priority over what is declared in `width` and `height` or `format`
         /// options. Defaults to `false`, which will scale the content to fit the paper size.
         prefer_css_page_size: Option<bool>,
         /// Paper margins, defaults to none.
-        margin: Option<PdfMargins<'g, 'h, 'i, 'j>>,
-        /// The file path to save the PDF to. If `path` is a relative path, then it is resolved relative to the current working
-        /// directory. If no path is provided, the PDF won't be saved to the disk.
-        path: Option<PathBuf>
+        margin: Option<Margin>,
+        /// Also writes the PDF to this file. A relative path is resolved relative to the current
+        /// working directory.
+        save_to: Option<PathBuf>
     }
 }
 
 pub struct ScreenshotBuilder {
     inner: Weak<Impl>,
-    args: ScreenshotArgs
+    args: ScreenshotArgs,
+    capture: CaptureArgs,
+    deadline: Option<Duration>
 }
 
 impl ScreenshotBuilder {
     pub(crate) fn new(inner: Weak<Impl>) -> Self {
         let args = ScreenshotArgs::default();
-        Self { inner, args }
+        Self {
+            inner,
+            args,
+            capture: CaptureArgs::default(),
+            deadline: None
+        }
     }
 
     pub async fn screenshot(self) -> ArcResult<Vec<u8>> {
-        let Self { inner, args } = self;
-        upgrade(&inner)?.screenshot(args).await
+        let Self {
+            inner,
+            args,
+            capture,
+            deadline
+        } = self;
+        let f = async move {
+            let page = upgrade(&inner)?;
+            let frame = Frame::new(page.main_frame());
+            let capture = capture.resolve(&frame).await?;
+            capture.around(&frame, page.screenshot(args)).await
+        };
+        with_deadline(deadline, f).await
     }
 
     pub fn r#type(mut self, x: ScreenshotType) -> Self {
@@ -844,6 +1096,10 @@ impl ScreenshotBuilder {
         self
     }
 
+    deadline! {}
+
+    capture! {}
+
     setter! {
         /// An object which specifies clipping of the resulting image. Should have the following fields:
         clip: Option<FloatRect>,
@@ -871,20 +1127,31 @@ impl ScreenshotBuilder {
 
 pub struct EmulateMediaBuilder {
     inner: Weak<Impl>,
-    args: EmulateMediaArgs
+    args: EmulateMediaArgs,
+    deadline: Option<Duration>
 }
 
 impl EmulateMediaBuilder {
     pub(crate) fn new(inner: Weak<Impl>) -> Self {
         let args = EmulateMediaArgs::default();
-        Self { inner, args }
+        Self {
+            inner,
+            args,
+            deadline: None
+        }
     }
 
     pub async fn emulate_media(self) -> ArcResult<()> {
-        let Self { inner, args } = self;
-        upgrade(&inner)?.emulate_media(args).await
+        let Self {
+            inner,
+            args,
+            deadline
+        } = self;
+        with_deadline(deadline, upgrade(&inner)?.emulate_media(args)).await
     }
 
+    deadline! {}
+
     setter! {
         /// Emulates `'prefers-colors-scheme'` media feature, supported values are `'light'`, `'dark'`, `'no-preference'`.
         // NOTE: Not implemented passing `null` disables color scheme emulation
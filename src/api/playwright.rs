@@ -9,7 +9,7 @@ use std::{io, process::Command};
 /// Entry point
 pub struct Playwright {
     driver: Driver,
-    _conn: Connection,
+    conn: Connection,
     inner: Weak<Impl>
 }
 
@@ -37,7 +37,34 @@ impl Playwright {
         let p = Impl::wait_initial_object(&conn).await?;
         Ok(Self {
             driver,
-            _conn: conn,
+            conn,
+            inner: p
+        })
+    }
+
+    /// Same as [`Playwright::with_driver`] and writes the whole protocol exchange to `path` as JSON lines.
+    pub async fn with_driver_recording<P: AsRef<Path>>(
+        driver: Driver,
+        path: P
+    ) -> Result<Playwright, Error> {
+        let conn = Connection::run_recording(&driver.executable(), path.as_ref())?;
+        let p = Impl::wait_initial_object(&conn).await?;
+        Ok(Self {
+            driver,
+            conn,
+            inner: p
+        })
+    }
+
+    /// Serves a session recorded by [`Playwright::with_driver_recording`] without launching the driver.
+    /// Requests must be sent in the recorded order with the same arguments,
+    /// otherwise they fail with a transport error describing the divergence.
+    pub async fn replay<P: AsRef<Path>>(path: P) -> Result<Playwright, Error> {
+        let conn = Connection::replay(path.as_ref())?;
+        let p = Impl::wait_initial_object(&conn).await?;
+        Ok(Self {
+            driver: Driver::new(Driver::default_dest()),
+            conn,
             inner: p
         })
     }
@@ -100,6 +127,25 @@ impl Playwright {
             .unwrap_or_default()
     }
 
+    /// Sets a client-side deadline for every request on this connection.
+    /// A request without response within `x` fails with [`Error::Timeout`], even if the driver hangs.
+    /// The operation in the driver is not cancelled.
+    pub fn set_default_deadline(&self, x: Option<Duration>) {
+        if let Some(c) = self.conn.context().upgrade() {
+            c.lock().unwrap().set_default_deadline(x);
+        }
+    }
+
+    /// Numbers of objects currently held by the client, keyed by protocol type such as `"Request"` or `"JSHandle"`.
+    /// Objects are released when the driver disposes them.
+    pub fn object_counts(&self) -> HashMap<String, usize> {
+        self.conn
+            .context()
+            .upgrade()
+            .map(|c| c.lock().unwrap().object_counts())
+            .unwrap_or_default()
+    }
+
     pub fn device(&self, name: &str) -> Option<DeviceDescriptor> {
         let inner = self.inner.upgrade()?;
         let device = inner.device(name)?;
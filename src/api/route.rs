@@ -95,18 +95,27 @@ impl Route {
 
 pub struct FulfillBuilder<'a, 'b> {
     inner: Weak<Impl>,
-    args: FulfillArgs<'a, 'b>
+    args: FulfillArgs<'a, 'b>,
+    deadline: Option<Duration>
 }
 
 impl<'a, 'b> FulfillBuilder<'a, 'b> {
     pub(crate) fn new(inner: Weak<Impl>, body: &'a str, is_base64: bool) -> Self {
         let args = FulfillArgs::new(body, is_base64);
-        Self { inner, args }
+        Self {
+            inner,
+            args,
+            deadline: None
+        }
     }
 
     pub async fn fulfill(self) -> Result<(), Arc<Error>> {
-        let Self { inner, args } = self;
-        upgrade(&inner)?.fulfill(args).await
+        let Self {
+            inner,
+            args,
+            deadline
+        } = self;
+        with_deadline(deadline, upgrade(&inner)?.fulfill(args)).await
     }
 
     /// Response headers. Header values will be converted to a string.
@@ -118,6 +127,8 @@ impl<'a, 'b> FulfillBuilder<'a, 'b> {
         self
     }
 
+    deadline! {}
+
     setter! {
         /// If set, equals to setting `Content-Type` response header.
         content_type: Option<&'b str>,
@@ -133,18 +144,27 @@ impl<'a, 'b> FulfillBuilder<'a, 'b> {
 
 pub struct ContinueBuilder<'a, 'b, 'c> {
     inner: Weak<Impl>,
-    args: ContinueArgs<'a, 'b, 'c>
+    args: ContinueArgs<'a, 'b, 'c>,
+    deadline: Option<Duration>
 }
 
 impl<'a, 'b, 'c> ContinueBuilder<'a, 'b, 'c> {
     pub(crate) fn new(inner: Weak<Impl>) -> Self {
         let args = ContinueArgs::default();
-        Self { inner, args }
+        Self {
+            inner,
+            args,
+            deadline: None
+        }
     }
 
     pub async fn r#continue(self) -> Result<(), Arc<Error>> {
-        let Self { inner, args } = self;
-        upgrade(&inner)?.r#continue(args).await
+        let Self {
+            inner,
+            args,
+            deadline
+        } = self;
+        with_deadline(deadline, upgrade(&inner)?.r#continue(args)).await
     }
 
     /// If set changes the request HTTP headers. Header values will be converted to a string.
@@ -156,6 +176,8 @@ impl<'a, 'b, 'c> ContinueBuilder<'a, 'b, 'c> {
         self
     }
 
+    deadline! {}
+
     setter! {
         /// If set changes the request method (e.g. GET or POST)
         method: Option<&'b str>,
@@ -1,3 +1,9 @@
+mod parser;
+
+pub use parser::{
+    parse, parse_with_engines, Engine, ParsedSelector, Part, SelectorBuilder, SelectorError
+};
+
 use crate::imp::{core::*, prelude::*, selectors::Selectors as Impl};
 
 /// Selectors can be used to install custom selector engines.
@@ -65,4 +71,16 @@ impl Selectors {
         let inner = upgrade(&self.inner)?;
         inner.register(name, script, content_script).await
     }
+
+    /// Parses `selector`, accepting the engines registered so far on this connection.
+    pub fn parse(&self, selector: &str) -> Result<ParsedSelector, Error> {
+        let engines = upgrade(&self.inner)?.engines();
+        Ok(parse_with_engines(selector, &engines)?)
+    }
+
+    /// Changes the attribute used by `get_by_test_id`. Defaults to `data-testid`.
+    /// Applies to builders created after the call on every page of this connection.
+    pub fn set_test_id_attribute(&self, name: &str) -> Result<(), Error> {
+        upgrade(&self.inner)?.set_test_id_attribute(name)
+    }
 }
@@ -0,0 +1,648 @@
+use std::{fmt, ops::Range};
+
+/// Selector engine of a [`Part`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Engine {
+    Css,
+    Xpath,
+    Text,
+    Id,
+    DataTestId,
+    DataTestIdDashed,
+    DataTest,
+    Nth,
+    /// Registered with [`Selectors::register`](crate::api::Selectors::register)
+    Custom(String)
+}
+
+impl Engine {
+    pub fn name(&self) -> &str {
+        match self {
+            Self::Css => "css",
+            Self::Xpath => "xpath",
+            Self::Text => "text",
+            Self::Id => "id",
+            Self::DataTestId => "data-testid",
+            Self::DataTestIdDashed => "data-test-id",
+            Self::DataTest => "data-test",
+            Self::Nth => "nth",
+            Self::Custom(s) => s.as_str()
+        }
+    }
+
+    fn builtin(name: &str) -> Option<Self> {
+        let e = match name {
+            "css" => Self::Css,
+            "xpath" => Self::Xpath,
+            "text" => Self::Text,
+            "id" => Self::Id,
+            "data-testid" => Self::DataTestId,
+            "data-test-id" => Self::DataTestIdDashed,
+            "data-test" => Self::DataTest,
+            "nth" => Self::Nth,
+            _ => return None
+        };
+        Some(e)
+    }
+
+    /// Whether `:light` can be appended to the name to skip shadow roots
+    fn has_light(&self) -> bool { !matches!(self, Self::Xpath | Self::Nth | Self::Custom(_)) }
+}
+
+/// One `engine=body` step of a selector chained with `>>`
+#[derive(Debug, Clone, PartialEq)]
+pub struct Part {
+    pub engine: Engine,
+    /// Doesn't pierce shadow roots, as in `css:light=`
+    pub light: bool,
+    pub body: String,
+    /// Byte range of the body in the parsed selector
+    pub span: Range<usize>
+}
+
+impl fmt::Display for Part {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let light = if self.light { ":light" } else { "" };
+        write!(f, "{}{}={}", self.engine.name(), light, self.body)
+    }
+}
+
+/// A validated selector. `Display` renders it back with explicit engine names.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParsedSelector {
+    pub parts: Vec<Part>,
+    /// Index of the part marked with `*`, whose element is returned instead of the last one
+    pub capture: Option<usize>
+}
+
+impl fmt::Display for ParsedSelector {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, p) in self.parts.iter().enumerate() {
+            if i > 0 {
+                f.write_str(" >> ")?;
+            }
+            if self.capture == Some(i) {
+                f.write_str("*")?;
+            }
+            write!(f, "{}", p)?;
+        }
+        Ok(())
+    }
+}
+
+/// The selector is malformed. `span` is a byte range in `selector`.
+#[derive(Debug, Clone, PartialEq, thiserror::Error)]
+pub struct SelectorError {
+    pub selector: String,
+    pub span: Range<usize>,
+    pub message: String
+}
+
+impl fmt::Display for SelectorError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "Invalid selector: {}", self.message)?;
+        writeln!(f, "{}", self.selector)?;
+        let count = |s: Option<&str>| s.map_or(0, |s| s.chars().count());
+        let start = count(self.selector.get(..self.span.start));
+        let len = count(self.selector.get(self.span.clone())).max(1);
+        write!(f, "{}{}", " ".repeat(start), "^".repeat(len))
+    }
+}
+
+/// Parses a selector that uses only built-in engines.
+pub fn parse(selector: &str) -> Result<ParsedSelector, SelectorError> {
+    parse_with_engines::<&str>(selector, &[])
+}
+
+/// Parses a selector that may use the custom engines named in `engines`.
+pub fn parse_with_engines<S: AsRef<str>>(
+    selector: &str,
+    engines: &[S]
+) -> Result<ParsedSelector, SelectorError> {
+    Parser { selector, engines }.parse()
+}
+
+struct Parser<'a, S> {
+    selector: &'a str,
+    engines: &'a [S]
+}
+
+impl<'a, S: AsRef<str>> Parser<'a, S> {
+    /// The span is clamped to the selector and widened to whole chars
+    fn error(&self, span: Range<usize>, message: impl Into<String>) -> SelectorError {
+        let s = self.selector;
+        let mut start = span.start.min(s.len());
+        while !s.is_char_boundary(start) {
+            start -= 1;
+        }
+        let mut end = span.end.clamp(start, s.len());
+        while !s.is_char_boundary(end) {
+            end += 1;
+        }
+        SelectorError {
+            selector: s.into(),
+            span: start..end,
+            message: message.into()
+        }
+    }
+
+    fn parse(&self) -> Result<ParsedSelector, SelectorError> {
+        let mut parts = Vec::new();
+        let mut capture = None;
+        for range in self.split()? {
+            let (part, captured) = self.part(range.clone())?;
+            if captured {
+                if capture.is_some() {
+                    return Err(self.error(range, "Only one part can be marked with *"));
+                }
+                capture = Some(parts.len());
+            }
+            parts.push(part);
+        }
+        Ok(ParsedSelector { parts, capture })
+    }
+
+    /// Byte ranges between top-level `>>`
+    fn split(&self) -> Result<Vec<Range<usize>>, SelectorError> {
+        let s = self.selector;
+        let bytes = s.as_bytes();
+        let mut ranges = Vec::new();
+        let mut quote: Option<(u8, usize)> = None;
+        let mut start = 0;
+        let mut i = 0;
+        while i < bytes.len() {
+            let c = bytes[i];
+            match quote {
+                _ if c == b'\\' && i + 1 < bytes.len() => {
+                    i += 2;
+                    continue;
+                }
+                Some((q, _)) if c == q => quote = None,
+                Some(_) => {}
+                None if c == b'"' || c == b'\'' || c == b'`' => quote = Some((c, i)),
+                None if c == b'>' && bytes.get(i + 1) == Some(&b'>') => {
+                    ranges.push(start..i);
+                    i += 2;
+                    start = i;
+                    continue;
+                }
+                None => {}
+            }
+            i += 1;
+        }
+        if let Some((_, at)) = quote {
+            return Err(self.error(at..s.len(), "Unterminated string"));
+        }
+        ranges.push(start..s.len());
+        Ok(ranges)
+    }
+
+    fn part(&self, range: Range<usize>) -> Result<(Part, bool), SelectorError> {
+        let raw = &self.selector[range.clone()];
+        let start = range.start + (raw.len() - raw.trim_start().len());
+        let text = raw.trim();
+        let end = start + text.len();
+        if text.is_empty() {
+            return Err(self.error(range, "Empty selector"));
+        }
+        let named = text.find('=').and_then(|eq| {
+            let name = text[..eq].trim();
+            let valid = !name.is_empty() &&
+                name.chars()
+                    .all(|c| c.is_ascii_alphanumeric() || "_-+:*".contains(c));
+            valid.then_some((name, eq))
+        });
+        let (name, body_start) = match named {
+            Some((name, eq)) => (name, start + eq + 1),
+            None if text.starts_with("//") || text.starts_with("..") => ("xpath", start),
+            None if text.starts_with('"') || text.starts_with('\'') => ("text", start),
+            None => ("css", start)
+        };
+        let (name, captured) = match name.strip_prefix('*') {
+            Some(n) => (n, true),
+            None => (name, false)
+        };
+        let (name, light) = match name.strip_suffix(":light") {
+            Some(n) => (n, true),
+            None => (name, false)
+        };
+        let name_span = start..start + text.find('=').unwrap_or(0);
+        let engine = match Engine::builtin(name) {
+            Some(e) if light && !e.has_light() => {
+                return Err(self.error(name_span, format!("{} has no :light variant", name)))
+            }
+            Some(e) => e,
+            None if !light && self.engines.iter().any(|e| e.as_ref() == name) => {
+                Engine::Custom(name.into())
+            }
+            None => return Err(self.error(name_span, format!("Unknown selector engine {:?}", name)))
+        };
+        let body = &self.selector[body_start..end];
+        let leading = body.len() - body.trim_start().len();
+        let body_span = body_start + leading..end;
+        let body = body.trim();
+        if body.is_empty() {
+            return Err(self.error(start..end, format!("Empty {} selector", engine.name())));
+        }
+        match engine {
+            Engine::Css => self.css(body, body_span.start)?,
+            Engine::Xpath => self.balanced(body, body_span.start)?,
+            Engine::Text => self.text(body, body_span.start)?,
+            Engine::Nth if body.parse::<i32>().is_err() => {
+                return Err(self.error(body_span, "nth= expects an integer"))
+            }
+            _ => {}
+        }
+        let part = Part {
+            engine,
+            light,
+            body: body.into(),
+            span: body_span
+        };
+        Ok((part, captured))
+    }
+
+    fn text(&self, body: &str, offset: usize) -> Result<(), SelectorError> {
+        let end = offset + body.len();
+        match body.chars().next() {
+            Some(q @ '"') | Some(q @ '\'') => {
+                let close = quoted_end(body, q)
+                    .ok_or_else(|| self.error(offset..end, "Unterminated string"))?;
+                if close != body.len() {
+                    return Err(self.error(
+                        offset + close..end,
+                        "Unexpected characters after quoted text"
+                    ));
+                }
+            }
+            Some('/') => {
+                let close = body[1..].rfind('/').map(|i| i + 1);
+                let close = match close {
+                    Some(c) if c > 1 => c,
+                    _ => return Err(self.error(offset..end, "Unterminated regular expression"))
+                };
+                if let Some((i, c)) = body[close + 1..]
+                    .char_indices()
+                    .find(|(_, c)| !"dgimsuy".contains(*c))
+                {
+                    let at = offset + close + 1 + i;
+                    return Err(self.error(
+                        at..at + c.len_utf8(),
+                        format!("Invalid regular expression flag {:?}", c)
+                    ));
+                }
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+
+    /// Quotes and brackets are closed.
+    fn balanced(&self, body: &str, offset: usize) -> Result<(), SelectorError> {
+        self.scan(body, offset, |_, i, _| Ok(i + 1))
+    }
+
+    fn css(&self, body: &str, offset: usize) -> Result<(), SelectorError> {
+        self.scan(body, offset, |p, i, rest| p.pseudo(body, offset, i, rest))
+    }
+
+    /// Walks `body` skipping strings, checking brackets, and calls `on_colon` at every `:` outside strings.
+    fn scan<F>(&self, body: &str, offset: usize, on_colon: F) -> Result<(), SelectorError>
+    where
+        F: Fn(&Self, usize, &str) -> Result<usize, SelectorError>
+    {
+        let mut stack: Vec<(char, usize)> = Vec::new();
+        let mut i = 0;
+        while i < body.len() {
+            let c = body[i..].chars().next().unwrap();
+            match c {
+                '\\' => {
+                    i += 1 + body[i + 1..].chars().next().map(char::len_utf8).unwrap_or(0);
+                    continue;
+                }
+                '"' | '\'' => {
+                    let close = quoted_end(&body[i..], c).ok_or_else(|| {
+                        self.error(offset + i..offset + body.len(), "Unterminated string")
+                    })?;
+                    i += close;
+                    continue;
+                }
+                '(' | '[' => stack.push((c, i)),
+                ')' | ']' => {
+                    let open = if c == ')' { '(' } else { '[' };
+                    match stack.pop() {
+                        Some((o, _)) if o == open => {}
+                        _ => {
+                            return Err(self.error(
+                                offset + i..offset + i + 1,
+                                format!("Unexpected {:?}", c)
+                            ))
+                        }
+                    }
+                }
+                ':' if stack.last().map(|(o, _)| *o) != Some('[') => {
+                    i = on_colon(self, i, &body[i..])?;
+                    continue;
+                }
+                _ => {}
+            }
+            i += c.len_utf8();
+        }
+        if let Some((o, at)) = stack.pop() {
+            let close = if o == '(' { ')' } else { ']' };
+            return Err(self.error(
+                offset + at..offset + body.len(),
+                format!("Missing {:?}", close)
+            ));
+        }
+        Ok(())
+    }
+
+    /// Checks the pseudo-class at `rest` and returns the index just after its name.
+    ///
+    /// Pseudo-classes are not checked against a list, since browsers keep adding new ones.
+    /// Only the arguments of Playwright's text pseudo-classes are validated.
+    fn pseudo(
+        &self,
+        body: &str,
+        offset: usize,
+        at: usize,
+        rest: &str
+    ) -> Result<usize, SelectorError> {
+        if let Some(element) = rest.strip_prefix("::") {
+            return Ok(at + 2 + ident_len(element));
+        }
+        let len = ident_len(&rest[1..]);
+        let name = &rest[1..1 + len];
+        let end = at + 1 + len;
+        let span = offset + at..offset + end;
+        if name.is_empty() {
+            return Err(self.error(span, "Expected a pseudo-class name after ':'"));
+        }
+        let lower = name.to_ascii_lowercase();
+        let strings = match &*lower {
+            "has-text" | "text" | "text-is" => 1..=1,
+            "text-matches" => 1..=2,
+            _ => return Ok(end)
+        };
+        let args = body[end..].strip_prefix('(').ok_or_else(|| {
+            self.error(span.clone(), format!(":{} expects a quoted string argument", name))
+        })?;
+        let mut n = 0;
+        let mut j = 0;
+        loop {
+            let trimmed = args[j..].trim_start();
+            j = args.len() - trimmed.len();
+            let q = trimmed.chars().next();
+            let arg_at = offset + end + 1 + j;
+            match q {
+                Some('"') | Some('\'') => {
+                    let close = quoted_end(trimmed, q.unwrap()).ok_or_else(|| {
+                        self.error(arg_at..offset + body.len(), "Unterminated string")
+                    })?;
+                    j += close;
+                    n += 1;
+                }
+                _ => {
+                    return Err(self.error(
+                        arg_at..arg_at + trimmed.find(')').unwrap_or(trimmed.len()).max(1),
+                        format!(":{} expects a quoted string argument", name)
+                    ))
+                }
+            }
+            let trimmed = args[j..].trim_start();
+            j = args.len() - trimmed.len();
+            match trimmed.chars().next() {
+                Some(',') if n < *strings.end() => j += 1,
+                Some(')') if strings.contains(&n) => return Ok(end + 1 + j + 1),
+                None => {
+                    return Err(self.error(
+                        offset + end..offset + body.len(),
+                        format!("Missing {:?}", ')')
+                    ))
+                }
+                _ => {
+                    let at = offset + end + 1 + j;
+                    return Err(self.error(
+                        at..at + 1,
+                        format!(":{} takes at most {} argument(s)", name, strings.end())
+                    ));
+                }
+            }
+        }
+    }
+}
+
+/// Byte index after the closing quote of the string at the start of `s`
+fn quoted_end(s: &str, quote: char) -> Option<usize> {
+    let mut escaped = false;
+    for (i, c) in s.char_indices().skip(1) {
+        match c {
+            _ if escaped => escaped = false,
+            '\\' => escaped = true,
+            _ if c == quote => return Some(i + 1),
+            _ => {}
+        }
+    }
+    None
+}
+
+fn ident_len(s: &str) -> usize {
+    s.char_indices()
+        .find(|(_, c)| !(c.is_alphanumeric() || *c == '-' || *c == '_'))
+        .map(|(i, _)| i)
+        .unwrap_or_else(|| s.len())
+}
+
+/// Composes a selector part by part.
+///
+/// ```
+/// # use playwright::api::selectors::SelectorBuilder;
+/// let s = SelectorBuilder::css("ul > li")
+///     .has_text("Apple")
+///     .nth(0)
+///     .text("Buy")
+///     .build()
+///     .unwrap();
+/// assert_eq!(
+///     s.to_string(),
+///     r#"css=ul > li:has-text("Apple") >> nth=0 >> text="Buy""#
+/// );
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct SelectorBuilder {
+    parts: Vec<(String, String)>,
+    capture: Option<usize>,
+    engines: Vec<String>
+}
+
+impl SelectorBuilder {
+    pub fn new() -> Self { Self::default() }
+
+    pub fn css(selector: &str) -> Self { Self::new().then_css(selector) }
+
+    pub fn then_css(self, selector: &str) -> Self { self.engine("css", selector) }
+
+    pub fn xpath(self, expression: &str) -> Self { self.engine("xpath", expression) }
+
+    /// Matches the whole text, case-sensitively after whitespace normalization.
+    pub fn text(self, text: &str) -> Self { self.engine("text", &quote(text)) }
+
+    /// Matches case-insensitive substrings.
+    pub fn text_contains(self, text: &str) -> Self { self.engine("text", text) }
+
+    pub fn text_regex(self, source: &str, flags: &str) -> Self {
+        self.engine("text", &format!("/{}/{}", source, flags))
+    }
+
+    pub fn id(self, id: &str) -> Self { self.engine("id", id) }
+
+    pub fn test_id(self, id: &str) -> Self { self.engine("data-testid", id) }
+
+    pub fn nth(self, index: i32) -> Self { self.engine("nth", &index.to_string()) }
+
+    /// Adds `:has-text()` to the last css part, or starts a css part matching any element.
+    pub fn has_text(mut self, text: &str) -> Self {
+        let pseudo = format!(":has-text({})", quote(text));
+        match self.parts.last_mut() {
+            Some((e, body)) if e == "css" => {
+                body.push_str(&pseudo);
+                self
+            }
+            _ => self.engine("css", &format!("*{}", pseudo))
+        }
+    }
+
+    /// Appends a part with any engine, including custom ones.
+    pub fn engine(mut self, name: &str, body: &str) -> Self {
+        if Engine::builtin(name).is_none() && !self.engines.iter().any(|e| e == name) {
+            self.engines.push(name.into());
+        }
+        self.parts.push((name.into(), body.into()));
+        self
+    }
+
+    /// Returns the element matched by the last part added so far instead of the final one.
+    pub fn capture(mut self) -> Self {
+        self.capture = self.parts.len().checked_sub(1);
+        self
+    }
+
+    pub fn build(&self) -> Result<ParsedSelector, SelectorError> {
+        parse_with_engines(&self.to_string(), &self.engines)
+    }
+}
+
+impl fmt::Display for SelectorBuilder {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, (name, body)) in self.parts.iter().enumerate() {
+            if i > 0 {
+                f.write_str(" >> ")?;
+            }
+            if self.capture == Some(i) {
+                f.write_str("*")?;
+            }
+            write!(f, "{}={}", name, body)?;
+        }
+        Ok(())
+    }
+}
+
+fn quote(s: &str) -> String { serde_json::Value::String(s.into()).to_string() }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn engines(s: &str) -> Vec<String> {
+        parse(s)
+            .unwrap()
+            .parts
+            .into_iter()
+            .map(|p| p.engine.name().to_owned())
+            .collect()
+    }
+
+    #[test]
+    fn implicit_engines() {
+        assert_eq!(engines("div.a"), ["css"]);
+        assert_eq!(engines("//div"), ["xpath"]);
+        assert_eq!(engines("\"Hello\""), ["text"]);
+        assert_eq!(engines("input[type=text]"), ["css"]);
+        assert_eq!(
+            engines("#a >> text=\"x >> y\" >> nth=-1 >> id:light=b"),
+            ["css", "text", "nth", "id"]
+        );
+    }
+
+    #[test]
+    fn spans() {
+        let p = parse("css=div >> *text='a'").unwrap();
+        assert_eq!(p.capture, Some(1));
+        assert_eq!(&"css=div >> *text='a'"[p.parts[1].span.clone()], "'a'");
+        assert_eq!(p.to_string(), "css=div >> *text='a'");
+    }
+
+    #[test]
+    fn errors() {
+        let e = parse("text=\"foo").unwrap_err();
+        assert_eq!(e.span, 5..9);
+        assert_eq!(e.message, "Unterminated string");
+        assert!(e.to_string().ends_with("\n     ^^^^"));
+        assert_eq!(parse("div >> ").unwrap_err().message, "Empty selector");
+        assert_eq!(parse("nth=a").unwrap_err().span, 4..5);
+        assert_eq!(parse("div:").unwrap_err().span, 3..4);
+        assert_eq!(parse("div:has-text(a)").unwrap_err().span, 13..14);
+        assert_eq!(parse("div[a=b").unwrap_err().message, "Missing ']'");
+        assert_eq!(parse("text=/a/z").unwrap_err().span, 8..9);
+        assert_eq!(
+            parse("tag=div").unwrap_err().message,
+            "Unknown selector engine \"tag\""
+        );
+        assert!(parse_with_engines("tag=div", &["tag"]).is_ok());
+    }
+
+    #[test]
+    fn error_spans() {
+        let e = parse("div:has-text(").unwrap_err();
+        assert_eq!(e.span, 13..13);
+        assert!(e.to_string().ends_with("\n             ^"));
+        let e = parse("div:text-matches(\"a\",").unwrap_err();
+        assert!(e.span.end <= e.selector.len());
+        e.to_string();
+        let e = parse("div:has-text('a'  é").unwrap_err();
+        assert_eq!(&e.selector[e.span.clone()], "é");
+        assert!(e.to_string().ends_with("\n                  ^"));
+        let e = SelectorError {
+            selector: "é".into(),
+            span: 1..5,
+            message: "m".into()
+        };
+        e.to_string();
+    }
+
+    #[test]
+    fn pseudo_classes() {
+        for s in &[
+            "video:fullscreen",
+            "input:autofill",
+            "input:user-invalid",
+            "dialog:modal"
+        ] {
+            assert_eq!(engines(s), ["css"]);
+        }
+        assert!(parse("a::before").is_ok());
+    }
+
+    #[test]
+    fn builder() {
+        let s = SelectorBuilder::css("div")
+            .capture()
+            .text_regex("^a$", "i")
+            .engine("tag", "span")
+            .build()
+            .unwrap();
+        assert_eq!(s.to_string(), "*css=div >> text=/^a$/i >> tag=span");
+        assert_eq!(s.parts[2].engine, Engine::Custom("tag".into()));
+    }
+}
@@ -0,0 +1,222 @@
+//! Reading HTML tables and ARIA grids.
+//!
+//! Header rows are the rows of `thead`, or the leading rows made only of `th` or `columnheader`
+//! cells. Cells spanning several rows or columns are repeated in each of them. Without a header
+//! row, columns are named by their index.
+//!
+//! ```ignore
+//! #[derive(Deserialize)]
+//! struct User {
+//!     #[serde(rename = "Name")]
+//!     name: String,
+//!     #[serde(rename = "Email")]
+//!     email: String
+//! }
+//!
+//! let users: Vec<User> = page
+//!     .extract_table_builder("#users")
+//!     .stable(500.0)
+//!     .deserialize()
+//!     .await?;
+//! ```
+use crate::{
+    api::{ElementHandle, Frame},
+    imp::{core::*, prelude::*}
+};
+
+/// Cell texts of a table, with spans expanded
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct Table {
+    pub headers: Vec<String>,
+    pub rows: Vec<Vec<String>>
+}
+
+impl Table {
+    /// Rows keyed by header
+    pub fn records(&self) -> Vec<HashMap<String, String>> {
+        self.rows
+            .iter()
+            .map(|r| self.headers.iter().cloned().zip(r.iter().cloned()).collect())
+            .collect()
+    }
+
+    /// Deserializes each row from a map of header to cell text
+    pub fn deserialize<T: DeserializeOwned>(&self) -> Result<Vec<T>, Error> {
+        Ok(serde_json::from_value(serde_json::to_value(self.records())?)?)
+    }
+}
+
+enum Target<'a> {
+    Selector(Frame, &'a str),
+    Element(&'a ElementHandle)
+}
+
+pub struct TableBuilder<'a> {
+    target: Target<'a>,
+    args: TableArgs,
+    deadline: Option<Duration>
+}
+
+#[derive(Debug, Default, Serialize)]
+struct TableArgs {
+    stable: Option<f64>,
+    timeout: Option<f64>
+}
+
+impl<'a> TableBuilder<'a> {
+    pub(crate) fn new(frame: Frame, selector: &'a str) -> Self {
+        Self {
+            target: Target::Selector(frame, selector),
+            args: TableArgs::default(),
+            deadline: None
+        }
+    }
+
+    pub(crate) fn element(element: &'a ElementHandle) -> Self {
+        Self {
+            target: Target::Element(element),
+            args: TableArgs::default(),
+            deadline: None
+        }
+    }
+
+    pub async fn extract(self) -> ArcResult<Table> {
+        let Self {
+            target,
+            args,
+            deadline
+        } = self;
+        let f = async {
+            match target {
+                Target::Selector(frame, selector) => {
+                    frame
+                        .evaluate_on_selector(selector, TABLE, Some(args))
+                        .await
+                }
+                Target::Element(e) => {
+                    let frame = e.owner_frame().await?.ok_or(Error::ObjectNotFound)?;
+                    let expression = format!("([e, args]) => ({})(e, args)", TABLE);
+                    frame.evaluate(&expression, (e, args)).await
+                }
+            }
+        };
+        with_deadline(deadline, f).await
+    }
+
+    /// Rows keyed by header. See [`Table::records`].
+    pub async fn rows(self) -> ArcResult<Vec<HashMap<String, String>>> {
+        Ok(self.extract().await?.records())
+    }
+
+    /// See [`Table::deserialize`].
+    pub async fn deserialize<T: DeserializeOwned>(self) -> ArcResult<Vec<T>> {
+        Ok(self.extract().await?.deserialize()?)
+    }
+
+    deadline! {}
+
+    setter! {
+        /// Waits until the number of rows has not changed for this many milliseconds before reading.
+        /// Useful for grids that load their rows incrementally.
+        stable: Option<f64>,
+        /// Maximum time in milliseconds to wait for a stable row count, defaults to `30000`.
+        timeout: Option<f64>
+    }
+}
+
+/// Takes the table, or an element containing one, and `{ stable, timeout }`
+const TABLE: &str = r#"async (root, { stable, timeout }) => {
+    const tables = 'table, [role=grid], [role=table], [role=treegrid]';
+    const roleOf = e => (e.getAttribute('role') || '').trim().split(/\s+/)[0];
+    const text = e => (e.innerText === undefined ? e.textContent : e.innerText).replace(/\s+/g, ' ').trim();
+    const find = () => root.matches(tables) ? root : root.querySelector(tables) || root;
+    const rowsOf = t => {
+        if (t.localName === 'table') {
+            return Array.from(t.rows).map(r => ({
+                head: r.parentElement.localName === 'thead',
+                cells: Array.from(r.cells).map(c => ({ c, header: c.localName === 'th', rowSpan: c.rowSpan, colSpan: c.colSpan }))
+            }));
+        }
+        const span = (c, a) => Math.max(1, parseInt(c.getAttribute(a), 10) || 1);
+        return Array.from(t.querySelectorAll('[role=row]'))
+            .filter(r => r.parentElement.closest(tables) === t)
+            .map(r => ({
+                head: false,
+                cells: Array.from(r.querySelectorAll('[role=cell], [role=gridcell], [role=columnheader], [role=rowheader]'))
+                    .filter(c => c.closest('[role=row]') === r)
+                    .map(c => ({ c, header: roleOf(c) === 'columnheader', rowSpan: span(c, 'aria-rowspan'), colSpan: span(c, 'aria-colspan') }))
+            }));
+    };
+    if (stable) {
+        const start = Date.now();
+        let count = rowsOf(find()).length;
+        let since = start;
+        while (Date.now() - since < stable) {
+            if (Date.now() - start > (timeout || 30000))
+                throw new Error(`Row count did not settle within ${timeout || 30000}ms`);
+            await new Promise(f => setTimeout(f, Math.min(100, stable)));
+            const n = rowsOf(find()).length;
+            if (n !== count) {
+                count = n;
+                since = Date.now();
+            }
+        }
+    }
+    const rows = rowsOf(find());
+    const grid = rows.map(() => []);
+    rows.forEach((r, y) => {
+        let x = 0;
+        for (const { c, rowSpan, colSpan } of r.cells) {
+            while (grid[y][x] !== undefined) x++;
+            const v = text(c);
+            for (let dy = 0; dy < Math.max(1, rowSpan) && y + dy < grid.length; dy++)
+                for (let dx = 0; dx < Math.max(1, colSpan); dx++)
+                    grid[y + dy][x + dx] = v;
+            x += Math.max(1, colSpan);
+        }
+    });
+    let head = 0;
+    while (head < rows.length && (rows[head].head || rows[head].cells.length && rows[head].cells.every(c => c.header)))
+        head++;
+    const width = Math.max(0, ...grid.map(r => r.length));
+    const headers = [];
+    for (let x = 0; x < width; x++) {
+        const parts = [];
+        for (let y = 0; y < head; y++) {
+            const v = grid[y][x];
+            if (v && !parts.includes(v)) parts.push(v);
+        }
+        let name = head ? parts.join(' ') : String(x);
+        for (let i = 2; headers.includes(name); i++)
+            name = `${parts.join(' ')} (${i})`;
+        headers.push(name);
+    }
+    const body = grid.slice(head).map(r => headers.map((_, x) => r[x] === undefined ? '' : r[x]));
+    return { headers, rows: body };
+}"#;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn records() {
+        #[derive(Debug, Deserialize, PartialEq)]
+        struct Row {
+            a: String,
+            b: String
+        }
+        let t = Table {
+            headers: vec!["a".into(), "b".into()],
+            rows: vec![vec!["1".into(), "2".into()]]
+        };
+        assert_eq!(t.records()[0]["b"], "2");
+        assert_eq!(
+            t.deserialize::<Row>().unwrap(),
+            vec![Row {
+                a: "1".into(),
+                b: "2".into()
+            }]
+        );
+    }
+}
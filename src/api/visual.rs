@@ -0,0 +1,442 @@
+//! Comparing screenshots with baseline images. Requires the `visual` feature.
+//!
+//! Pixels are compared by perceived color difference, as in
+//! [pixelmatch](https://github.com/mapbox/pixelmatch). A missing baseline is written from the
+//! screenshot. Set `update(true)`, or the environment variable `PLAYWRIGHT_UPDATE_SNAPSHOTS`, to
+//! overwrite baselines instead of comparing.
+//!
+//! On mismatch `<name>-actual.png`, `<name>-expected.png` and `<name>-diff.png` are written next
+//! to the baseline, or into `output_dir`. Differing pixels are red in the diff, anti-aliased ones
+//! yellow.
+//!
+//! ```ignore
+//! page.compare_screenshot_builder("tests/baselines/home.png")
+//!     .add_mask(".clock")
+//!     .disable_animations(true)
+//!     .max_diff_pixel_ratio(0.01)
+//!     .compare()
+//!     .await?;
+//! ```
+use crate::{
+    api::{ElementHandle, Mask, Page},
+    imp::{core::*, prelude::*, utils::ScreenshotAnimations}
+};
+use image::{Rgba, RgbaImage};
+use std::{env, fmt};
+
+enum Target<'a> {
+    Page(&'a Page),
+    Element(&'a ElementHandle)
+}
+
+pub struct CompareScreenshotBuilder<'a> {
+    target: Target<'a>,
+    baseline: PathBuf,
+    args: CompareArgs,
+    deadline: Option<Duration>
+}
+
+#[derive(Debug, Default)]
+struct CompareArgs {
+    threshold: Option<f64>,
+    max_diff_pixels: Option<usize>,
+    max_diff_pixel_ratio: Option<f64>,
+    ignore_anti_aliasing: Option<bool>,
+    full_page: Option<bool>,
+    mask: Option<Vec<String>>,
+    disable_animations: Option<bool>,
+    update: Option<bool>,
+    output_dir: Option<PathBuf>
+}
+
+/// Result of a comparison that passed
+#[derive(Debug, Clone, PartialEq)]
+pub struct Comparison {
+    pub diff_pixels: usize,
+    pub total_pixels: usize,
+    /// The baseline was missing or updated and has been written from the screenshot
+    pub baseline_written: bool
+}
+
+/// The screenshot differs from the baseline by more than allowed
+#[derive(Debug, Clone, thiserror::Error)]
+pub struct Mismatch {
+    pub baseline: PathBuf,
+    /// `None` when the sizes differ
+    pub diff_pixels: Option<usize>,
+    pub total_pixels: usize,
+    pub actual: PathBuf,
+    pub expected: PathBuf,
+    pub diff: Option<PathBuf>
+}
+
+impl fmt::Display for Mismatch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.diff_pixels {
+            Some(n) => write!(
+                f,
+                "Screenshot differs from {:?} in {} of {} pixels",
+                self.baseline, n, self.total_pixels
+            )?,
+            None => write!(f, "Screenshot size differs from {:?}", self.baseline)?
+        }
+        write!(f, ". Actual: {:?}, expected: {:?}", self.actual, self.expected)?;
+        if let Some(diff) = &self.diff {
+            write!(f, ", diff: {:?}", diff)?;
+        }
+        Ok(())
+    }
+}
+
+impl<'a> CompareScreenshotBuilder<'a> {
+    pub(crate) fn page(page: &'a Page, baseline: PathBuf) -> Self {
+        Self {
+            target: Target::Page(page),
+            baseline,
+            args: CompareArgs::default(),
+            deadline: None
+        }
+    }
+
+    pub(crate) fn element(element: &'a ElementHandle, baseline: PathBuf) -> Self {
+        Self {
+            target: Target::Element(element),
+            baseline,
+            args: CompareArgs::default(),
+            deadline: None
+        }
+    }
+
+    /// Takes the screenshot and compares it. Fails with [`Mismatch`] when it differs by more than
+    /// allowed.
+    pub async fn compare(self) -> ArcResult<Comparison> {
+        let Self {
+            target,
+            baseline,
+            args,
+            deadline
+        } = self;
+        // Only the screenshot waits on the browser, the comparison is local
+        let actual = with_deadline(deadline, capture(&target, &args)).await?;
+        let update = args.update.unwrap_or_else(|| env::var_os(UPDATE).is_some());
+        if update || !baseline.exists() {
+            write_png(&baseline, &actual)?;
+            return Ok(Comparison {
+                diff_pixels: 0,
+                total_pixels: pixels(&actual),
+                baseline_written: true
+            });
+        }
+        let expected = image::open(&baseline).map_err(Error::from)?.to_rgba8();
+        let total_pixels = pixels(&actual).max(pixels(&expected));
+        let outputs = Outputs::new(&baseline, args.output_dir.as_deref());
+        if actual.dimensions() != expected.dimensions() {
+            write_png(&outputs.actual, &actual)?;
+            write_png(&outputs.expected, &expected)?;
+            return Err(Error::from(outputs.mismatch(baseline, None, total_pixels)).into());
+        }
+        let threshold = args.threshold.unwrap_or(0.1);
+        let ignore_anti_aliasing = args.ignore_anti_aliasing.unwrap_or(true);
+        let (diff_pixels, diff) = compare(&actual, &expected, threshold, ignore_anti_aliasing);
+        let allowed = match (args.max_diff_pixels, args.max_diff_pixel_ratio) {
+            (None, None) => 0,
+            (n, r) => n
+                .unwrap_or(usize::MAX)
+                .min(r.map_or(usize::MAX, |r| (r * total_pixels as f64) as usize))
+        };
+        if diff_pixels > allowed {
+            write_png(&outputs.actual, &actual)?;
+            write_png(&outputs.expected, &expected)?;
+            write_png(&outputs.diff, &diff)?;
+            let m = outputs.mismatch(baseline, Some(diff_pixels), total_pixels);
+            return Err(Error::from(m).into());
+        }
+        Ok(Comparison {
+            diff_pixels,
+            total_pixels,
+            baseline_written: false
+        })
+    }
+
+    /// Adds a selector whose elements are painted over before capturing
+    pub fn add_mask(mut self, selector: &str) -> Self {
+        self.args
+            .mask
+            .get_or_insert_with(Vec::new)
+            .push(selector.into());
+        self
+    }
+
+    deadline! {}
+
+    setter! {
+        /// Color difference in the range `0.0..=1.0` above which pixels differ. Defaults to `0.1`.
+        threshold: Option<f64>,
+        /// Number of differing pixels tolerated. Defaults to none.
+        max_diff_pixels: Option<usize>,
+        /// Ratio of differing pixels to all pixels tolerated. Defaults to none.
+        max_diff_pixel_ratio: Option<f64>,
+        /// Whether pixels that differ by anti-aliasing are not counted. Defaults to `true`.
+        ignore_anti_aliasing: Option<bool>,
+        /// Captures the full scrollable page. Ignored for elements.
+        full_page: Option<bool>,
+        /// Selectors whose elements are painted over before capturing
+        mask: Option<Vec<String>>,
+        /// Finishes finite CSS animations and transitions and cancels infinite ones before capturing
+        disable_animations: Option<bool>,
+        /// Overwrites the baseline instead of comparing
+        update: Option<bool>,
+        /// Where to write images on mismatch. Defaults to the baseline's directory.
+        output_dir: Option<PathBuf>
+    }
+}
+
+const UPDATE: &str = "PLAYWRIGHT_UPDATE_SNAPSHOTS";
+
+async fn capture(target: &Target<'_>, args: &CompareArgs) -> ArcResult<RgbaImage> {
+    let mask: Vec<Mask> = args
+        .mask
+        .iter()
+        .flatten()
+        .map(|s| s.as_str().into())
+        .collect();
+    let animations = match args.disable_animations {
+        Some(true) => ScreenshotAnimations::Disabled,
+        _ => ScreenshotAnimations::Allow
+    };
+    let bytes = match target {
+        Target::Page(p) => {
+            let mut builder = p.screenshot_builder().mask(mask).animations(animations);
+            if let Some(x) = args.full_page {
+                builder = builder.full_page(x);
+            }
+            builder.screenshot().await?
+        }
+        Target::Element(e) => {
+            e.screenshot_builder()
+                .await
+                .mask(mask)
+                .animations(animations)
+                .screenshot()
+                .await?
+        }
+    };
+    Ok(image::load_from_memory(&bytes)
+        .map_err(Error::from)?
+        .to_rgba8())
+}
+
+fn pixels(img: &RgbaImage) -> usize { (img.width() * img.height()) as usize }
+
+fn write_png(path: &Path, img: &RgbaImage) -> Result<(), Error> {
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir)?;
+    }
+    img.save_with_format(path, image::ImageFormat::Png)?;
+    Ok(())
+}
+
+struct Outputs {
+    actual: PathBuf,
+    expected: PathBuf,
+    diff: PathBuf
+}
+
+impl Outputs {
+    fn new(baseline: &Path, output_dir: Option<&Path>) -> Self {
+        let dir = output_dir
+            .or_else(|| baseline.parent())
+            .unwrap_or_else(|| Path::new(""));
+        let stem = baseline
+            .file_stem()
+            .map(|s| s.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        let path = |suffix: &str| dir.join(format!("{}-{}.png", stem, suffix));
+        Self {
+            actual: path("actual"),
+            expected: path("expected"),
+            diff: path("diff")
+        }
+    }
+
+    fn mismatch(self, baseline: PathBuf, diff_pixels: Option<usize>, total: usize) -> Mismatch {
+        let Outputs {
+            actual,
+            expected,
+            diff
+        } = self;
+        Mismatch {
+            baseline,
+            diff_pixels,
+            total_pixels: total,
+            actual,
+            expected,
+            diff: diff_pixels.map(|_| diff)
+        }
+    }
+}
+
+/// Largest possible value of [`delta`]
+const MAX_DELTA: f64 = 35215.0;
+
+/// Counts differing pixels of images of the same size and draws the diff
+fn compare(
+    actual: &RgbaImage,
+    expected: &RgbaImage,
+    threshold: f64,
+    ignore_anti_aliasing: bool
+) -> (usize, RgbaImage) {
+    let (w, h) = actual.dimensions();
+    let max_delta = MAX_DELTA * threshold * threshold;
+    let mut diff = RgbaImage::new(w, h);
+    let mut count = 0;
+    for (x, y, a) in actual.enumerate_pixels() {
+        let e = expected.get_pixel(x, y);
+        let d = delta(a, e, false);
+        let color = if d.abs() <= max_delta {
+            let gray = blend(y_of(a), 0.1 * f64::from(a[3]) / 255.0) as u8;
+            Rgba([gray, gray, gray, 255])
+        } else if ignore_anti_aliasing
+            && (anti_aliased(actual, x, y, expected) || anti_aliased(expected, x, y, actual))
+        {
+            Rgba([255, 255, 0, 255])
+        } else {
+            count += 1;
+            Rgba([255, 0, 0, 255])
+        };
+        diff.put_pixel(x, y, color);
+    }
+    (count, diff)
+}
+
+fn blend(c: f64, a: f64) -> f64 { 255.0 + (c - 255.0) * a }
+
+/// Color blended over white
+fn rgb(p: &Rgba<u8>) -> (f64, f64, f64) {
+    let a = f64::from(p[3]) / 255.0;
+    (
+        blend(f64::from(p[0]), a),
+        blend(f64::from(p[1]), a),
+        blend(f64::from(p[2]), a)
+    )
+}
+
+fn y_of(p: &Rgba<u8>) -> f64 {
+    let (r, g, b) = rgb(p);
+    r * 0.29889531 + g * 0.58662247 + b * 0.11448223
+}
+
+/// Squared YIQ distance, negative when `b` is brighter
+fn delta(a: &Rgba<u8>, b: &Rgba<u8>, brightness_only: bool) -> f64 {
+    if a == b {
+        return 0.0;
+    }
+    let ((r1, g1, b1), (r2, g2, b2)) = (rgb(a), rgb(b));
+    let (y1, y2) = (y_of(a), y_of(b));
+    let y = y1 - y2;
+    if brightness_only {
+        return y;
+    }
+    let i = (r1 * 0.59597799 - g1 * 0.2741761 - b1 * 0.32180189)
+        - (r2 * 0.59597799 - g2 * 0.2741761 - b2 * 0.32180189);
+    let q = (r1 * 0.21147017 - g1 * 0.52261711 + b1 * 0.31114694)
+        - (r2 * 0.21147017 - g2 * 0.52261711 + b2 * 0.31114694);
+    let d = 0.5053 * y * y + 0.299 * i * i + 0.1957 * q * q;
+    if y1 > y2 {
+        -d
+    } else {
+        d
+    }
+}
+
+/// Whether the pixel looks like anti-aliasing: it sits between darker and brighter neighbours
+/// that are each part of a flat area in both images
+fn anti_aliased(img: &RgbaImage, x: u32, y: u32, other: &RgbaImage) -> bool {
+    let (w, h) = img.dimensions();
+    let (x0, y0) = (x.saturating_sub(1), y.saturating_sub(1));
+    let (x2, y2) = ((x + 1).min(w - 1), (y + 1).min(h - 1));
+    let center = img.get_pixel(x, y);
+    let mut zeroes = if x == x0 || x == x2 || y == y0 || y == y2 {
+        1
+    } else {
+        0
+    };
+    let (mut min, mut max) = (0.0, 0.0);
+    let (mut darkest, mut brightest) = ((0, 0), (0, 0));
+    for nx in x0..=x2 {
+        for ny in y0..=y2 {
+            if (nx, ny) == (x, y) {
+                continue;
+            }
+            let d = delta(center, img.get_pixel(nx, ny), true);
+            if d == 0.0 {
+                zeroes += 1;
+                if zeroes > 2 {
+                    return false;
+                }
+            } else if d < min {
+                min = d;
+                darkest = (nx, ny);
+            } else if d > max {
+                max = d;
+                brightest = (nx, ny);
+            }
+        }
+    }
+    if min == 0.0 || max == 0.0 {
+        return false;
+    }
+    let flat = |(x, y): (u32, u32)| many_siblings(img, x, y) && many_siblings(other, x, y);
+    flat(darkest) || flat(brightest)
+}
+
+/// Whether at least 3 neighbours have the same color, counting the image edge as one
+fn many_siblings(img: &RgbaImage, x: u32, y: u32) -> bool {
+    let (w, h) = img.dimensions();
+    let (x0, y0) = (x.saturating_sub(1), y.saturating_sub(1));
+    let (x2, y2) = ((x + 1).min(w - 1), (y + 1).min(h - 1));
+    let center = img.get_pixel(x, y);
+    let mut zeroes = if x == x0 || x == x2 || y == y0 || y == y2 {
+        1
+    } else {
+        0
+    };
+    for nx in x0..=x2 {
+        for ny in y0..=y2 {
+            if (nx, ny) != (x, y) && img.get_pixel(nx, ny) == center {
+                zeroes += 1;
+                if zeroes > 2 {
+                    return true;
+                }
+            }
+        }
+    }
+    false
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn diff() {
+        let white = Rgba([255, 255, 255, 255]);
+        let a = RgbaImage::from_pixel(8, 8, white);
+        let mut b = a.clone();
+        b.put_pixel(4, 4, Rgba([0, 0, 0, 255]));
+        b.put_pixel(1, 1, Rgba([250, 250, 250, 255]));
+        let (n, diff) = compare(&a, &b, 0.1, true);
+        assert_eq!(n, 1);
+        assert_eq!(diff.get_pixel(4, 4), &Rgba([255, 0, 0, 255]));
+        assert_eq!(compare(&a, &b, 1.0, true).0, 0);
+        assert_eq!(compare(&a, &a, 0.0, false).0, 0);
+    }
+
+    #[test]
+    fn outputs() {
+        let o = Outputs::new(Path::new("base/home.png"), None);
+        assert_eq!(o.diff, Path::new("base/home-diff.png"));
+        let o = Outputs::new(Path::new("home.png"), Some(Path::new("out")));
+        assert_eq!(o.actual, Path::new("out/home-actual.png"));
+    }
+}
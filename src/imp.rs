@@ -8,6 +8,7 @@ pub(crate) mod prelude {
         value::{to_value, Value}
     };
     pub use std::{
+        borrow::Cow,
         collections::HashMap,
         convert::{TryFrom, TryInto},
         future::Future,
@@ -70,8 +71,7 @@ mod macros {
         ($r: expr, $method:literal, $args: expr) => {{
             let m: Str<Method> = $method.to_owned().try_into().unwrap();
             let r = $r.channel().create_request(m).set_args($args)?;
-            let fut = $r.channel().send_message(r).await?;
-            let res = fut.await?;
+            let res = $r.channel().request(r).await?;
             let res = res.map_err(Error::ErrorResponded)?;
             res
         }};
@@ -83,12 +83,16 @@ pub(crate) mod core {
     mod driver;
     mod event_emitter;
     mod message;
+    mod recording;
     mod remote_object;
+    #[cfg(feature = "tracing")]
+    pub(crate) mod trace;
     mod transport;
     pub use connection::*;
     pub use driver::*;
     pub use event_emitter::*;
     pub use message::*;
+    pub use recording::*;
     pub(crate) use remote_object::*;
     pub use transport::*;
 }
@@ -109,6 +113,8 @@ pub(crate) mod element_handle;
 pub(crate) mod file_hooser;
 pub(crate) mod frame;
 pub(crate) mod js_handle;
+#[cfg(feature = "log-forward")]
+pub(crate) mod log_forward;
 pub(crate) mod page;
 pub(crate) mod request;
 pub(crate) mod response;
@@ -14,18 +14,22 @@ pub(crate) struct Context {
     ctx: Wm<Context>,
     id: i32,
     callbacks: HashMap<i32, WaitPlaces<WaitMessageResult>>,
-    writer: Writer
+    writer: Writer,
+    default_deadline: Option<Duration>,
+    test_id_attribute: String
 }
 
 #[derive(Debug)]
 pub(crate) struct Connection {
-    _child: Child,
+    _child: Option<Child>,
     ctx: Am<Context>,
     reader: Am<Reader>,
     should_stop: Arc<AtomicBool>
 }
 
+/// Variants are added with new features, some of them only exist with cargo features enabled.
 #[derive(thiserror::Error, Debug)]
+#[non_exhaustive]
 pub enum Error {
     #[error(transparent)]
     Io(#[from] io::Error),
@@ -69,8 +73,28 @@ pub enum Error {
     ResolvePath(PathBuf),
     #[error("Timed out")]
     Timeout,
+    #[error("Strict mode violation: {0:?} resolved to {1} elements")]
+    StrictModeViolation(String, usize),
+    #[error("Element is not an iframe: {0:?}")]
+    NotIframe(String),
+    #[error("Source and target of a drag are in different frames")]
+    DragAcrossFrames,
+    #[error("Mask element is not in the frame that is captured")]
+    MaskAcrossFrames,
     #[error(transparent)]
-    Join(#[from] JoinError)
+    Assertion(#[from] crate::api::expect::AssertionFailure),
+    #[error(transparent)]
+    InvalidSelector(#[from] crate::api::selectors::SelectorError),
+    #[error(transparent)]
+    PdfOption(#[from] crate::imp::utils::PdfOptionError),
+    #[error(transparent)]
+    Join(#[from] JoinError),
+    #[cfg(feature = "visual")]
+    #[error(transparent)]
+    Image(#[from] image::ImageError),
+    #[cfg(feature = "visual")]
+    #[error(transparent)]
+    ScreenshotMismatch(#[from] crate::api::visual::Mismatch)
 }
 
 pub(crate) type ArcResult<T> = Result<T, Arc<Error>>;
@@ -83,29 +107,71 @@ impl Drop for Connection {
 }
 
 impl Connection {
-    fn try_new(exec: &Path) -> io::Result<Connection> {
-        let mut child = Command::new(exec)
+    fn try_new(exec: &Path, record: Option<&Path>) -> io::Result<Connection> {
+        let mut command = Command::new(exec);
+        command
             .args(&["run-driver"])
             .stdin(Stdio::piped())
             .stdout(Stdio::piped())
-            .stderr(Stdio::null())
-            .spawn()?;
+            .stderr(Stdio::null());
+        #[cfg(feature = "log-forward")]
+        {
+            command.stderr(Stdio::piped());
+            if std::env::var_os("DEBUG").is_none()
+                && log::log_enabled!(target: crate::imp::log_forward::DRIVER_TARGET, log::Level::Debug)
+            {
+                command.env("DEBUG", "pw:api");
+            }
+        }
+        let mut child = command.spawn()?;
         // TODO: env "NODE_OPTIONS"
+        #[cfg(feature = "log-forward")]
+        {
+            if let Some(stderr) = child.stderr.take() {
+                crate::imp::log_forward::spawn_driver_stderr(stderr);
+            }
+        }
         let stdin = child.stdin.take().unwrap();
         let stdout = child.stdout.take().unwrap();
-        let reader = Reader::new(stdout);
-        let writer = Writer::new(stdin);
+        let mut reader = Reader::new(stdout);
+        let mut writer = Writer::new(stdin);
+        if let Some(path) = record {
+            let recorder = Arc::new(Mutex::new(Recorder::create(path)?));
+            reader.set_recorder(recorder.clone());
+            writer.set_recorder(recorder);
+        }
+        Ok(Self::with_transport(Some(child), reader, writer))
+    }
+
+    fn with_transport(child: Option<Child>, reader: Reader, writer: Writer) -> Connection {
         let ctx = Context::new(writer);
-        Ok(Self {
+        Self {
             _child: child,
             ctx,
             should_stop: Arc::new(false.into()),
             reader: Arc::new(Mutex::new(reader))
-        })
+        }
     }
 
     pub(crate) fn run(exec: &Path) -> io::Result<Connection> {
-        let conn = Self::try_new(exec)?;
+        let conn = Self::try_new(exec, None)?;
+        conn.start();
+        Ok(conn)
+    }
+
+    /// Runs the driver and writes every message to `path`
+    pub(crate) fn run_recording(exec: &Path, path: &Path) -> io::Result<Connection> {
+        let conn = Self::try_new(exec, Some(path))?;
+        conn.start();
+        Ok(conn)
+    }
+
+    /// Serves a recording written by [`Connection::run_recording`] without the driver
+    pub(crate) fn replay(path: &Path) -> Result<Connection, Error> {
+        let replay = Arc::new(Mutex::new(Replay::load(path)?));
+        let reader = Reader::replay(replay.clone());
+        let writer = Writer::replay(replay);
+        let conn = Self::with_transport(None, reader, writer);
         conn.start();
         Ok(conn)
     }
@@ -192,7 +258,9 @@ impl Context {
             ctx: Weak::new(),
             id: 0,
             callbacks: HashMap::new(),
-            writer
+            writer,
+            default_deadline: None,
+            test_id_attribute: "data-testid".into()
         };
         let am = Arc::new(Mutex::new(ctx));
         am.lock().unwrap().ctx = Arc::downgrade(&am);
@@ -210,8 +278,15 @@ impl Context {
     fn dispatch(&mut self, msg: Res) -> Result<(), Error> {
         match msg {
             Res::Result(msg) => {
-                let p = self.callbacks.get(&msg.id).ok_or(Error::CallbackNotFound)?;
-                Self::respond_wait(p, Ok(msg.body.map(Arc::new).map_err(Arc::new)));
+                let p = match self.callbacks.remove(&msg.id) {
+                    Some(p) => p,
+                    None => {
+                        // The request was dropped or timed out on the client
+                        log::trace!("Response to forgotten request {}", msg.id);
+                        return Ok(());
+                    }
+                };
+                Self::respond_wait(&p, Ok(msg.body.map(Arc::new).map_err(Arc::new)));
                 return Ok(());
             }
             Res::Initial(msg) => {
@@ -226,6 +301,8 @@ impl Context {
                 }
                 let target = self.objects.get(&msg.guid).ok_or(Error::ObjectNotFound)?;
                 let ResInitial { method, params, .. } = msg;
+                #[cfg(feature = "tracing")]
+                let _entered = trace::event(target.channel(), &method).entered();
                 target.handle_event(self, method, params)?;
             }
         }
@@ -235,7 +312,7 @@ impl Context {
     fn dispose(&mut self, i: &S<Guid>) {
         let a = match self.objects.get(i) {
             None => return,
-            Some(a) => a
+            Some(a) => a.clone()
         };
         let cs = a.channel().children();
         for c in cs {
@@ -245,9 +322,25 @@ impl Context {
             };
             self.dispose(&c.channel().guid);
         }
+        if let Some(p) = a.channel().parent.as_ref().and_then(RemoteWeak::upgrade) {
+            p.channel().remove_child(i);
+        }
         self.remove_object(i);
     }
 
+    /// Numbers of live objects by protocol type
+    pub(crate) fn object_counts(&self) -> HashMap<String, usize> {
+        let mut counts = HashMap::new();
+        for o in self.objects.values() {
+            let typ = o.channel().typ.as_str();
+            if typ.is_empty() {
+                continue;
+            }
+            *counts.entry(typ.to_owned()).or_insert(0) += 1;
+        }
+        counts
+    }
+
     fn respond_wait(
         WaitPlaces { value, waker }: &WaitPlaces<WaitMessageResult>,
         result: WaitMessageResult
@@ -308,7 +401,17 @@ impl Context {
 
     pub(in crate::imp) fn remove_object(&mut self, k: &S<Guid>) { self.objects.remove(k); }
 
-    pub(in crate::imp::core) fn send_message(&mut self, r: RequestBody) -> Result<(), Error> {
+    pub(in crate::imp::core) fn remove_callback(&mut self, id: i32) { self.callbacks.remove(&id); }
+
+    pub(crate) fn default_deadline(&self) -> Option<Duration> { self.default_deadline }
+
+    pub(crate) fn set_default_deadline(&mut self, x: Option<Duration>) { self.default_deadline = x; }
+
+    pub(crate) fn test_id_attribute(&self) -> &str { &self.test_id_attribute }
+
+    pub(crate) fn set_test_id_attribute(&mut self, x: String) { self.test_id_attribute = x; }
+
+    pub(in crate::imp::core) fn send_message(&mut self, r: RequestBody) -> Result<i32, Error> {
         self.id += 1;
         let RequestBody {
             guid,
@@ -317,24 +420,77 @@ impl Context {
             place
         } = r;
         self.callbacks.insert(self.id, place);
+        #[cfg(feature = "tracing")]
+        trace::record_id(self.id);
         let req = Req {
             guid: &guid,
             method: &method,
             params,
             id: self.id
         };
-        self.writer.send(&req)?;
-        Ok(())
+        if let Err(e) = self.writer.send(&req) {
+            self.callbacks.remove(&self.id);
+            return Err(e.into());
+        }
+        Ok(self.id)
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::imp::core::*;
+    use crate::imp::{core::*, prelude::*};
+
+    #[test]
+    fn dispose_removes_children() {
+        let writer = Writer::replay(Arc::new(Mutex::new(Replay::default())));
+        let ctx = Context::new(writer);
+        let mut ctx = ctx.lock().unwrap();
+        let create = |parent: &str, typ: &str, guid: &str| {
+            let msg = serde_json::json!({
+                "guid": parent,
+                "method": "__create__",
+                "params": {"type": typ, "guid": guid, "initializer": {}}
+            });
+            serde_json::from_value::<Res>(msg).unwrap()
+        };
+        ctx.dispatch(create("", "Foo", "foo")).unwrap();
+        ctx.dispatch(create("foo", "Bar", "bar1")).unwrap();
+        ctx.dispatch(create("foo", "Bar", "bar2")).unwrap();
+        ctx.dispatch(create("bar1", "Baz", "baz")).unwrap();
+        assert_eq!(ctx.object_counts().get("Bar"), Some(&2));
+        let dispose = |guid: &str| {
+            let msg = serde_json::json!({"guid": guid, "method": "__dispose__", "params": {}});
+            serde_json::from_value::<Res>(msg).unwrap()
+        };
+        ctx.dispatch(dispose("bar1")).unwrap();
+        let counts = ctx.object_counts();
+        assert_eq!(counts.get("Bar"), Some(&1));
+        assert_eq!(counts.get("Baz"), None);
+        let guid: &S<Guid> = S::validate("foo").unwrap();
+        let foo = ctx.objects.get(guid).unwrap();
+        assert_eq!(foo.channel().children().len(), 1);
+        ctx.dispatch(dispose("foo")).unwrap();
+        assert!(ctx.object_counts().is_empty());
+    }
+
+    #[test]
+    fn spawn_detached_outside_runtime() {
+        let (tx, rx) = std::sync::mpsc::channel();
+        spawn_detached(async move { tx.send(()).unwrap() });
+        rx.recv_timeout(Duration::from_secs(5)).unwrap();
+    }
+
+    crate::runtime_test!(deadline, {
+        let never = futures::future::pending::<ArcResult<()>>();
+        let r = with_deadline(Some(Duration::from_millis(10)), never).await;
+        assert!(matches!(r.as_ref().map_err(|e| &**e), Err(Error::Timeout)));
+        let r = with_deadline(Some(Duration::from_secs(10)), async { Ok(1) }).await;
+        assert_eq!(r.unwrap(), 1);
+    });
 
     crate::runtime_test!(start, {
         let driver = Driver::install().unwrap();
-        let conn = Connection::try_new(&driver.executable()).unwrap();
+        let conn = Connection::try_new(&driver.executable(), None).unwrap();
         Connection::start(&conn);
     });
 }
@@ -1,5 +1,10 @@
 pub(crate) mod de;
+mod js_value;
 pub(crate) mod ser;
+mod wrap;
+
+pub use js_value::JsValue;
+pub use wrap::{Bytes, JsMap, JsSet};
 
 use crate::imp::core::Error;
 use serde::{Deserialize, Deserializer};
@@ -178,7 +183,7 @@ pub(crate) struct Argument {
     pub(crate) handles: Vec<OnlyGuid>
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Clone, PartialEq)]
 pub struct DateTime {
     d: String
 }
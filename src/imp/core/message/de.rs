@@ -1,6 +1,7 @@
+use super::js_value::JS_VALUE;
 use serde::{
     de,
-    de::{IntoDeserializer, Visitor}
+    de::{value::SeqDeserializer, IntoDeserializer, Visitor}
 };
 use serde_json::value::{Map, Value};
 use std::convert::TryFrom;
@@ -16,7 +17,9 @@ pub enum Error {
     #[error("{0:} isn't supported")]
     NotSupported(&'static str),
     #[error(transparent)]
-    Serde(#[from] serde_json::Error)
+    Serde(#[from] serde_json::Error),
+    #[error(transparent)]
+    InvalidBase64(#[from] base64::DecodeError)
 }
 
 impl de::Error for Error {
@@ -41,6 +44,14 @@ impl<'de> Deserializer<'de> {
     fn pop(&mut self) -> Result<&'de Value, Error> { self.stack.pop().ok_or(Error::Blank) }
 }
 
+/// Contents of a typed array, `{"ta": {"b": base64, "k": kind}}`
+fn typed_array(v: &Value) -> Option<(Result<Vec<u8>, Error>, &str)> {
+    let ta = v.as_object()?.get("ta")?;
+    let b = ta.get("b").and_then(|b| b.as_str())?;
+    let k = ta.get("k").and_then(|k| k.as_str())?;
+    Some((base64::decode(b).map_err(Error::from), k))
+}
+
 pub(crate) fn from_value<T>(v: &Value) -> Result<T, Error>
 where
     T: de::DeserializeOwned
@@ -102,10 +113,13 @@ impl<'de, 'a> de::Deserializer<'de> for &'a mut Deserializer<'de> {
                             self.deserialize_any(visitor)
                         }
                     }
-                } else if let Some(_a) = m.get("a") {
+                } else if m.contains_key("a") || m.contains_key("se") || m.contains_key("ta") {
                     self.deserialize_seq(visitor)
-                } else if let Some(_o) = m.get("o") {
+                } else if m.contains_key("o") || m.contains_key("m") {
                     self.deserialize_map(visitor)
+                } else if let Some(Value::String(s)) = m.get("bi") {
+                    let _ = self.pop()?;
+                    visitor.visit_borrowed_str(s)
                 } else if let Some(n) = m.get("n") {
                     match n {
                         Value::Number(x) if x.is_u64() => self.deserialize_u64(visitor),
@@ -151,6 +165,42 @@ impl<'de, 'a> de::Deserializer<'de> for &'a mut Deserializer<'de> {
     int! {u32, u64}
     int! {u64, u64}
 
+    fn deserialize_i128<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>
+    {
+        let v = self.pop()?;
+        let i = match v.as_object().and_then(|m| m.get("bi")).and_then(|v| v.as_str()) {
+            Some(s) => s.parse().map_err(|_| Error::TypeMismatch)?,
+            None => v
+                .as_object()
+                .and_then(|m| m.get("n"))
+                .unwrap_or(v)
+                .as_i64()
+                .ok_or(Error::TypeMismatch)?
+                .into()
+        };
+        visitor.visit_i128(i)
+    }
+
+    fn deserialize_u128<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>
+    {
+        let v = self.pop()?;
+        let i = match v.as_object().and_then(|m| m.get("bi")).and_then(|v| v.as_str()) {
+            Some(s) => s.parse().map_err(|_| Error::TypeMismatch)?,
+            None => v
+                .as_object()
+                .and_then(|m| m.get("n"))
+                .unwrap_or(v)
+                .as_u64()
+                .ok_or(Error::TypeMismatch)?
+                .into()
+        };
+        visitor.visit_u128(i)
+    }
+
     fn deserialize_char<V>(self, _: V) -> Result<V::Value, Self::Error>
     where
         V: Visitor<'de>
@@ -158,18 +208,26 @@ impl<'de, 'a> de::Deserializer<'de> for &'a mut Deserializer<'de> {
         Err(Error::NotSupported("deserialize_char"))
     }
 
-    fn deserialize_bytes<V>(self, _visitor: V) -> Result<V::Value, Self::Error>
+    fn deserialize_bytes<V>(self, visitor: V) -> Result<V::Value, Self::Error>
     where
         V: Visitor<'de>
     {
-        Err(Error::NotSupported("deserialize_bytes"))
+        self.deserialize_byte_buf(visitor)
     }
 
-    fn deserialize_byte_buf<V>(self, _visitor: V) -> Result<V::Value, Self::Error>
+    /// Raw contents of a typed array, or an array of numbers
+    fn deserialize_byte_buf<V>(self, visitor: V) -> Result<V::Value, Self::Error>
     where
         V: Visitor<'de>
     {
-        Err(Error::NotSupported("deserialize_byte_buf"))
+        let v = *self.stack.last().ok_or(Error::Blank)?;
+        match typed_array(v) {
+            Some((bytes, _)) => {
+                let _ = self.pop()?;
+                visitor.visit_byte_buf(bytes?)
+            }
+            None => self.deserialize_seq(visitor)
+        }
     }
 
     fn deserialize_f32<V>(self, _: V) -> Result<V::Value, Self::Error>
@@ -286,12 +344,19 @@ impl<'de, 'a> de::Deserializer<'de> for &'a mut Deserializer<'de> {
 
     fn deserialize_newtype_struct<V>(
         self,
-        _name: &'static str,
+        name: &'static str,
         visitor: V
     ) -> Result<V::Value, Self::Error>
     where
         V: Visitor<'de>
     {
+        if name == JS_VALUE {
+            let v = self.pop()?;
+            return visitor.visit_map(Raw {
+                key: Some(JS_VALUE),
+                value: v
+            });
+        }
         visitor.visit_newtype_struct(self)
     }
 
@@ -300,6 +365,17 @@ impl<'de, 'a> de::Deserializer<'de> for &'a mut Deserializer<'de> {
         V: Visitor<'de>
     {
         let v = self.pop()?;
+        if let Some((bytes, kind)) = typed_array(v) {
+            if kind != "ui8" && kind != "ui8c" {
+                return Err(Error::NotSupported("typed arrays other than Uint8Array"));
+            }
+            return visitor.visit_seq(SeqDeserializer::new(bytes?.into_iter()));
+        }
+        // Elements of a `Set`
+        let v = v
+            .as_object()
+            .and_then(|m| m.get("se"))
+            .unwrap_or(v);
         let a1 = v
             .as_object()
             .and_then(|m| m.get("a"))
@@ -342,6 +418,14 @@ impl<'de, 'a> de::Deserializer<'de> for &'a mut Deserializer<'de> {
             .ok_or(Error::TypeMismatch);
         if m.contains_key("v") || m.contains_key("a") {
             Err(Error::TypeMismatch)
+        } else if let Some(pairs) = m.get("m") {
+            // Entries of a `Map` are `[key, value]` arrays
+            let pairs = pairs
+                .as_object()
+                .and_then(|m| m.get("a"))
+                .and_then(|v| v.as_array())
+                .ok_or(Error::TypeMismatch)?;
+            visitor.visit_map(Pairs::new(self, pairs))
         } else if m.contains_key("d") {
             visitor.visit_map(Object::new(&mut self, m))
         } else if m.contains_key("o") {
@@ -527,6 +611,81 @@ impl<'de, 'a> de::MapAccess<'de> for ObjectArr<'a, 'de> {
     }
 }
 
+struct Pairs<'a, 'de: 'a> {
+    prime: &'a mut Deserializer<'de>,
+    data: std::slice::Iter<'de, Value>,
+    value: Option<&'de Value>
+}
+
+impl<'a, 'de> Pairs<'a, 'de> {
+    fn new(prime: &'a mut Deserializer<'de>, pairs: &'de [Value]) -> Self {
+        Self {
+            prime,
+            data: pairs.iter(),
+            value: None
+        }
+    }
+}
+
+impl<'de, 'a> de::MapAccess<'de> for Pairs<'a, 'de> {
+    type Error = Error;
+
+    fn next_key_seed<K>(&mut self, seed: K) -> Result<Option<K::Value>, Self::Error>
+    where
+        K: de::DeserializeSeed<'de>
+    {
+        let pair = match self.data.next() {
+            Some(x) => x,
+            None => return Ok(None)
+        };
+        let kv = pair
+            .as_object()
+            .and_then(|m| m.get("a"))
+            .and_then(|v| v.as_array())
+            .filter(|kv| kv.len() == 2)
+            .ok_or(Error::TypeMismatch)?;
+        self.value = Some(&kv[1]);
+        self.prime.stack.push(&kv[0]);
+        seed.deserialize(&mut *self.prime).map(Some)
+    }
+
+    fn next_value_seed<V>(&mut self, seed: V) -> Result<V::Value, Self::Error>
+    where
+        V: de::DeserializeSeed<'de>
+    {
+        let data = self.value.take().ok_or(Error::Blank)?;
+        self.prime.stack.push(data);
+        seed.deserialize(&mut *self.prime)
+    }
+}
+
+/// A single entry map of `key` to the value as it is on the wire
+struct Raw<'de> {
+    key: Option<&'static str>,
+    value: &'de Value
+}
+
+impl<'de> de::MapAccess<'de> for Raw<'de> {
+    type Error = Error;
+
+    fn next_key_seed<K>(&mut self, seed: K) -> Result<Option<K::Value>, Self::Error>
+    where
+        K: de::DeserializeSeed<'de>
+    {
+        match self.key.take() {
+            Some(k) => seed.deserialize(k.into_deserializer()).map(Some),
+            None => Ok(None)
+        }
+    }
+
+    fn next_value_seed<V>(&mut self, seed: V) -> Result<V::Value, Self::Error>
+    where
+        V: de::DeserializeSeed<'de>
+    {
+        Ok(seed.deserialize(self.value)?)
+    }
+}
+
 struct KeyDeserializer<'a, 'de: 'a> {
     prime: &'a mut Deserializer<'de>,
     s: &'de str
@@ -774,4 +933,33 @@ mod tests {
         let de: Test = from_value(&v).unwrap();
         assert_eq!(de, Test::Struct { a: 0 });
     }
+
+    #[test]
+    fn js_types() {
+        use super::super::{ser, Bytes, DateTime, JsMap, JsSet};
+        use serde::{de::DeserializeOwned, Serialize};
+        fn round_trip<T: Serialize + DeserializeOwned>(x: &T) -> T {
+            from_value(&ser::to_value(x).unwrap()["value"]).unwrap()
+        }
+        let bytes = Bytes(vec![0, 255]);
+        assert_eq!(round_trip(&bytes), bytes);
+        let v = serde_json::json!({"ta": {"b": "AP8=", "k": "ui8"}});
+        assert_eq!(from_value::<Vec<u8>>(&v).unwrap(), vec![0, 255]);
+        let v = serde_json::json!({"a": [{"n": 1}, {"n": 2}]});
+        assert_eq!(from_value::<Bytes>(&v).unwrap(), Bytes(vec![1, 2]));
+        assert_eq!(round_trip(&i128::from(i64::MIN)), i128::from(i64::MIN));
+        assert_eq!(from_value::<u128>(&serde_json::json!({"n": 3})).unwrap(), 3);
+        let m = JsMap(vec![(2, "b".to_owned()), (1, "a".to_owned())]);
+        assert_eq!(round_trip(&m), m);
+        let v = serde_json::json!({"m": {"a": [{"a": [{"n": 2}, {"s": "b"}]}]}});
+        assert_eq!(from_value::<JsMap<i32, String>>(&v).unwrap().0, &m.0[..1]);
+        let hm: HashMap<String, i32> = m.0.iter().map(|(k, v)| (v.clone(), *k)).collect();
+        assert_eq!(round_trip(&hm), hm);
+        let set = JsSet(vec!["x".to_owned()]);
+        assert_eq!(round_trip(&set), set);
+        let date = DateTime {
+            d: "2021-01-01T00:00:00.000Z".into()
+        };
+        assert_eq!(round_trip(&date), date);
+    }
 }
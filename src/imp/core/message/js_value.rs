@@ -0,0 +1,572 @@
+use super::{de, DateTime};
+use serde::{
+    de::{MapAccess, SeqAccess, Visitor},
+    ser::{Error as _, SerializeMap, SerializeSeq, SerializeStruct},
+    Deserialize, Deserializer, Serialize, Serializer
+};
+use serde_json::value::Value;
+use std::fmt;
+
+/// Asks [`de::Deserializer`] for the raw protocol value
+pub(crate) const JS_VALUE: &str = "0f3c6d3e-5b1a-4e0e-9a57-2d8c1b7f4e21";
+pub(crate) const REGEXP: &str = "c5d9a2e4-7f18-4b3a-8e62-1d4f0b9a6c37";
+pub(crate) const HANDLE: &str = "2e7b4c91-d05a-4f6e-a3b8-9c1e5f7d2a48";
+
+/// Any JavaScript value that can cross the protocol, kept as is.
+///
+/// Unlike [`serde_json::Value`] this distinguishes `undefined` from `null`, keeps `NaN`,
+/// `Infinity` and `-0`, and knows dates and regular expressions.
+///
+/// BigInts, maps, sets and typed arrays are read from drivers that send them, but the bundled
+/// driver can't receive them, so serializing these variants fails.
+///
+/// ```ignore
+/// let v: JsValue = page.evaluate("() => [1, NaN, undefined]", ()).await?;
+/// assert_eq!(v.to_string(), "[1, NaN, undefined]");
+/// ```
+#[derive(Debug, Clone)]
+pub enum JsValue {
+    Undefined,
+    Null,
+    Bool(bool),
+    Number(f64),
+    /// Decimal digits of the BigInt, without the `n` suffix. Read only, as it can't be sent to
+    /// the driver.
+    BigInt(String),
+    String(String),
+    Date(DateTime),
+    RegExp {
+        source: String,
+        flags: String
+    },
+    Array(Vec<JsValue>),
+    /// Properties in enumeration order
+    Object(Vec<(String, JsValue)>),
+    /// Entries in insertion order. Read only, as it can't be sent to the driver.
+    Map(Vec<(JsValue, JsValue)>),
+    /// Read only, as it can't be sent to the driver.
+    Set(Vec<JsValue>),
+    /// Raw contents of a typed array. `kind` is as in the protocol, like `ui8` or `f64`.
+    /// Read only, as it can't be sent to the driver.
+    TypedArray {
+        kind: String,
+        bytes: Vec<u8>
+    },
+    /// Index into the handles sent along with the value
+    Handle(usize)
+}
+
+impl JsValue {
+    pub fn is_undefined(&self) -> bool { matches!(self, Self::Undefined) }
+
+    pub fn is_nullish(&self) -> bool { matches!(self, Self::Undefined | Self::Null) }
+
+    pub fn as_bool(&self) -> Option<bool> {
+        match self {
+            Self::Bool(b) => Some(*b),
+            _ => None
+        }
+    }
+
+    pub fn as_f64(&self) -> Option<f64> {
+        match self {
+            Self::Number(n) => Some(*n),
+            _ => None
+        }
+    }
+
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            Self::String(s) => Some(s),
+            _ => None
+        }
+    }
+
+    pub fn as_array(&self) -> Option<&[JsValue]> {
+        match self {
+            Self::Array(xs) => Some(xs),
+            _ => None
+        }
+    }
+
+    /// Value of the property `key` of an object
+    pub fn get(&self, key: &str) -> Option<&JsValue> {
+        match self {
+            Self::Object(entries) => entries.iter().find(|(k, _)| k == key).map(|(_, v)| v),
+            _ => None
+        }
+    }
+
+    /// Reads a serialized value of the protocol
+    pub(crate) fn from_protocol(v: &Value) -> Result<Self, de::Error> {
+        let m = v.as_object().ok_or(de::Error::TypeMismatch)?;
+        let (k, x) = match (m.len(), m.iter().next()) {
+            (1, Some(e)) => e,
+            _ => return Err(de::Error::TypeMismatch)
+        };
+        let v = match (k.as_str(), x) {
+            ("v", Value::String(s)) => match s.as_str() {
+                "undefined" => Self::Undefined,
+                "null" => Self::Null,
+                "NaN" => Self::Number(f64::NAN),
+                "Infinity" => Self::Number(f64::INFINITY),
+                "-Infinity" => Self::Number(f64::NEG_INFINITY),
+                "-0" => Self::Number(-0.0),
+                _ => return Err(de::Error::TypeMismatch)
+            },
+            ("b", Value::Bool(b)) => Self::Bool(*b),
+            ("n", Value::Number(n)) => Self::Number(n.as_f64().ok_or(de::Error::TypeMismatch)?),
+            ("s", Value::String(s)) => Self::String(s.clone()),
+            ("bi", Value::String(s)) => Self::BigInt(s.clone()),
+            ("d", Value::String(s)) => Self::Date(DateTime { d: s.clone() }),
+            ("r", Value::Object(r)) => {
+                let part = |k: &str| {
+                    r.get(k)
+                        .and_then(Value::as_str)
+                        .map(String::from)
+                        .ok_or(de::Error::TypeMismatch)
+                };
+                Self::RegExp {
+                    source: part("p")?,
+                    flags: part("f")?
+                }
+            }
+            ("a", Value::Array(xs)) => {
+                Self::Array(xs.iter().map(Self::from_protocol).collect::<Result<_, _>>()?)
+            }
+            ("o", Value::Array(entries)) => {
+                let entry = |e: &Value| {
+                    let k = e.get("k").and_then(Value::as_str);
+                    let v = e.get("v");
+                    match (k, v) {
+                        (Some(k), Some(v)) => Ok((k.to_owned(), Self::from_protocol(v)?)),
+                        _ => Err(de::Error::TypeMismatch)
+                    }
+                };
+                Self::Object(entries.iter().map(entry).collect::<Result<_, _>>()?)
+            }
+            ("m", Value::Object(m)) => {
+                let pair = |e: &Value| match e.get("a").and_then(Value::as_array) {
+                    Some(kv) if kv.len() == 2 => {
+                        Ok((Self::from_protocol(&kv[0])?, Self::from_protocol(&kv[1])?))
+                    }
+                    _ => Err(de::Error::TypeMismatch)
+                };
+                let pairs = m.get("a").and_then(Value::as_array);
+                let pairs = pairs.ok_or(de::Error::TypeMismatch)?;
+                Self::Map(pairs.iter().map(pair).collect::<Result<_, _>>()?)
+            }
+            ("se", x) => match Self::from_protocol(x)? {
+                Self::Array(xs) => Self::Set(xs),
+                _ => return Err(de::Error::TypeMismatch)
+            },
+            ("ta", Value::Object(ta)) => {
+                let part = |k: &str| {
+                    ta.get(k)
+                        .and_then(Value::as_str)
+                        .ok_or(de::Error::TypeMismatch)
+                };
+                Self::TypedArray {
+                    kind: part("k")?.into(),
+                    bytes: base64::decode(part("b")?)?
+                }
+            }
+            ("h", Value::Number(n)) => {
+                Self::Handle(n.as_u64().ok_or(de::Error::TypeMismatch)? as usize)
+            }
+            _ => return Err(de::Error::TypeMismatch)
+        };
+        Ok(v)
+    }
+}
+
+fn same_number(a: f64, b: f64) -> bool {
+    a.to_bits() == b.to_bits() || (a.is_nan() && b.is_nan())
+}
+
+/// Equal as the same value would be in JavaScript's `Object.is`, applied deeply
+impl PartialEq for JsValue {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Self::Undefined, Self::Undefined) | (Self::Null, Self::Null) => true,
+            (Self::Bool(a), Self::Bool(b)) => a == b,
+            (Self::Number(a), Self::Number(b)) => same_number(*a, *b),
+            (Self::BigInt(a), Self::BigInt(b)) | (Self::String(a), Self::String(b)) => a == b,
+            (Self::Date(a), Self::Date(b)) => a == b,
+            (
+                Self::RegExp {
+                    source: s1,
+                    flags: f1
+                },
+                Self::RegExp {
+                    source: s2,
+                    flags: f2
+                }
+            ) => s1 == s2 && f1 == f2,
+            (Self::Array(a), Self::Array(b)) => a == b,
+            (Self::Object(a), Self::Object(b)) => a == b,
+            (Self::Map(a), Self::Map(b)) => a == b,
+            (Self::Set(a), Self::Set(b)) => a == b,
+            (
+                Self::TypedArray {
+                    kind: k1,
+                    bytes: b1
+                },
+                Self::TypedArray {
+                    kind: k2,
+                    bytes: b2
+                }
+            ) => k1 == k2 && b1 == b2,
+            (Self::Handle(a), Self::Handle(b)) => a == b,
+            _ => false
+        }
+    }
+}
+
+impl From<bool> for JsValue {
+    fn from(b: bool) -> Self { Self::Bool(b) }
+}
+
+impl From<f64> for JsValue {
+    fn from(n: f64) -> Self { Self::Number(n) }
+}
+
+impl From<&str> for JsValue {
+    fn from(s: &str) -> Self { Self::String(s.into()) }
+}
+
+impl From<String> for JsValue {
+    fn from(s: String) -> Self { Self::String(s) }
+}
+
+impl From<Value> for JsValue {
+    fn from(v: Value) -> Self {
+        match v {
+            Value::Null => Self::Null,
+            Value::Bool(b) => Self::Bool(b),
+            Value::Number(n) => Self::Number(n.as_f64().unwrap_or(f64::NAN)),
+            Value::String(s) => Self::String(s),
+            Value::Array(xs) => Self::Array(xs.into_iter().map(Self::from).collect()),
+            Value::Object(m) => Self::Object(m.into_iter().map(|(k, v)| (k, v.into())).collect())
+        }
+    }
+}
+
+/// Like `console.log` in the browser
+impl fmt::Display for JsValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result { self.write(f, false) }
+}
+
+impl JsValue {
+    fn write(&self, f: &mut fmt::Formatter<'_>, nested: bool) -> fmt::Result {
+        match self {
+            Self::Undefined => write!(f, "undefined"),
+            Self::Null => write!(f, "null"),
+            Self::Bool(b) => write!(f, "{}", b),
+            Self::Number(n) if n.is_nan() => write!(f, "NaN"),
+            Self::Number(n) if n.is_infinite() => {
+                write!(f, "{}", if *n > 0.0 { "Infinity" } else { "-Infinity" })
+            }
+            Self::Number(n) if *n == 0.0 && n.is_sign_negative() => write!(f, "-0"),
+            Self::Number(n) => write!(f, "{}", n),
+            Self::BigInt(s) => write!(f, "{}n", s),
+            Self::String(s) if nested => write_quoted(f, s),
+            Self::String(s) => write!(f, "{}", s),
+            Self::Date(d) => write!(f, "{}", d.d),
+            Self::RegExp { source, flags } => write!(f, "/{}/{}", source, flags),
+            Self::Array(xs) => {
+                write!(f, "[")?;
+                for (i, x) in xs.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ", ")?;
+                    }
+                    x.write(f, true)?;
+                }
+                write!(f, "]")
+            }
+            Self::Object(entries) if entries.is_empty() => write!(f, "{{}}"),
+            Self::Object(entries) => {
+                write!(f, "{{ ")?;
+                for (i, (k, v)) in entries.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ", ")?;
+                    }
+                    if is_identifier(k) {
+                        write!(f, "{}", k)?;
+                    } else {
+                        write_quoted(f, k)?;
+                    }
+                    write!(f, ": ")?;
+                    v.write(f, true)?;
+                }
+                write!(f, " }}")
+            }
+            Self::Map(entries) => {
+                write!(f, "Map({}) {{", entries.len())?;
+                for (i, (k, v)) in entries.iter().enumerate() {
+                    write!(f, "{}", if i > 0 { ", " } else { " " })?;
+                    k.write(f, true)?;
+                    write!(f, " => ")?;
+                    v.write(f, true)?;
+                }
+                write!(f, "{}}}", if entries.is_empty() { "" } else { " " })
+            }
+            Self::Set(xs) => {
+                write!(f, "Set({}) {{", xs.len())?;
+                for (i, x) in xs.iter().enumerate() {
+                    write!(f, "{}", if i > 0 { ", " } else { " " })?;
+                    x.write(f, true)?;
+                }
+                write!(f, "{}}}", if xs.is_empty() { "" } else { " " })
+            }
+            Self::TypedArray { kind, bytes } => write!(f, "TypedArray({}) {:?}", kind, bytes),
+            Self::Handle(i) => write!(f, "JSHandle@{}", i)
+        }
+    }
+}
+
+fn is_identifier(s: &str) -> bool {
+    let mut cs = s.chars();
+    let head = |c: char| c.is_alphabetic() || c == '_' || c == '$';
+    match cs.next() {
+        Some(c) if head(c) => cs.all(|c| head(c) || c.is_numeric()),
+        _ => false
+    }
+}
+
+fn write_quoted(f: &mut fmt::Formatter<'_>, s: &str) -> fmt::Result {
+    write!(f, "'")?;
+    for c in s.chars() {
+        match c {
+            '\'' => write!(f, "\\'")?,
+            '\\' => write!(f, "\\\\")?,
+            '\n' => write!(f, "\\n")?,
+            c => write!(f, "{}", c)?
+        }
+    }
+    write!(f, "'")
+}
+
+impl Serialize for JsValue {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer
+    {
+        match self {
+            Self::Undefined => serializer.serialize_unit(),
+            Self::Null => serializer.serialize_none(),
+            Self::Bool(b) => serializer.serialize_bool(*b),
+            // Integers go as integers so that they stay exact on the wire
+            Self::Number(n)
+                if n.fract() == 0.0
+                    && n.abs() <= 9007199254740991.0
+                    && !(*n == 0.0 && n.is_sign_negative()) =>
+            {
+                serializer.serialize_i64(*n as i64)
+            }
+            Self::Number(n) => serializer.serialize_f64(*n),
+            Self::BigInt(_) => Err(S::Error::custom("BigInt can't be sent to the driver")),
+            Self::String(s) => serializer.serialize_str(s),
+            Self::Date(d) => d.serialize(serializer),
+            Self::RegExp { source, flags } => {
+                let mut x = serializer.serialize_struct(REGEXP, 2)?;
+                x.serialize_field("p", source)?;
+                x.serialize_field("f", flags)?;
+                x.end()
+            }
+            Self::Array(xs) => {
+                let mut x = serializer.serialize_seq(Some(xs.len()))?;
+                for v in xs {
+                    x.serialize_element(v)?;
+                }
+                x.end()
+            }
+            Self::Object(entries) => {
+                let mut x = serializer.serialize_map(Some(entries.len()))?;
+                for (k, v) in entries {
+                    x.serialize_entry(k, v)?;
+                }
+                x.end()
+            }
+            // The driver reads none of these. See `JsMap`, `JsSet` and `Bytes` for sending them.
+            Self::Map(_) => Err(S::Error::custom("Map can't be sent to the driver")),
+            Self::Set(_) => Err(S::Error::custom("Set can't be sent to the driver")),
+            Self::TypedArray { .. } => {
+                Err(S::Error::custom("typed arrays can't be sent to the driver"))
+            }
+            Self::Handle(i) => {
+                let mut x = serializer.serialize_struct(HANDLE, 1)?;
+                x.serialize_field("h", i)?;
+                x.end()
+            }
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for JsValue {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>
+    {
+        deserializer.deserialize_newtype_struct(JS_VALUE, JsValueVisitor)
+    }
+}
+
+/// The protocol deserializer answers with a map of [`JS_VALUE`] to the raw value.
+/// Other deserializers are read like JSON.
+struct JsValueVisitor;
+
+impl<'de> Visitor<'de> for JsValueVisitor {
+    type Value = JsValue;
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "a JavaScript value")
+    }
+
+    fn visit_newtype_struct<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: Deserializer<'de>
+    {
+        deserializer.deserialize_any(self)
+    }
+
+    fn visit_unit<E>(self) -> Result<Self::Value, E> { Ok(JsValue::Null) }
+
+    fn visit_none<E>(self) -> Result<Self::Value, E> { Ok(JsValue::Null) }
+
+    fn visit_some<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: Deserializer<'de>
+    {
+        JsValue::deserialize(deserializer)
+    }
+
+    fn visit_bool<E>(self, b: bool) -> Result<Self::Value, E> { Ok(JsValue::Bool(b)) }
+
+    fn visit_i64<E>(self, n: i64) -> Result<Self::Value, E> { Ok(JsValue::Number(n as f64)) }
+
+    fn visit_u64<E>(self, n: u64) -> Result<Self::Value, E> { Ok(JsValue::Number(n as f64)) }
+
+    fn visit_f64<E>(self, n: f64) -> Result<Self::Value, E> { Ok(JsValue::Number(n)) }
+
+    fn visit_str<E>(self, s: &str) -> Result<Self::Value, E> { Ok(JsValue::String(s.into())) }
+
+    fn visit_string<E>(self, s: String) -> Result<Self::Value, E> { Ok(JsValue::String(s)) }
+
+    fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: SeqAccess<'de>
+    {
+        let mut xs = Vec::new();
+        while let Some(x) = seq.next_element()? {
+            xs.push(x);
+        }
+        Ok(JsValue::Array(xs))
+    }
+
+    fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+    where
+        A: MapAccess<'de>
+    {
+        let mut entries = Vec::new();
+        while let Some(k) = map.next_key::<String>()? {
+            if k == JS_VALUE && entries.is_empty() {
+                let raw: Value = map.next_value()?;
+                return JsValue::from_protocol(&raw).map_err(serde::de::Error::custom);
+            }
+            entries.push((k, map.next_value()?));
+        }
+        Ok(JsValue::Object(entries))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{super::ser, *};
+
+    fn round_trip(v: &JsValue) -> JsValue {
+        let serialized = ser::to_value(v).unwrap();
+        de::from_value(&serialized["value"]).unwrap()
+    }
+
+    #[test]
+    fn protocol() {
+        let v = JsValue::Object(vec![
+            ("u".into(), JsValue::Undefined),
+            ("n".into(), JsValue::Null),
+            ("nan".into(), f64::NAN.into()),
+            ("inf".into(), f64::NEG_INFINITY.into()),
+            ("zero".into(), (-0.0).into()),
+            ("int".into(), 3.0.into()),
+            ("float".into(), 0.5.into()),
+            (
+                "date".into(),
+                JsValue::Date(DateTime {
+                    d: "2021-01-01T00:00:00.000Z".into()
+                })
+            ),
+            (
+                "re".into(),
+                JsValue::RegExp {
+                    source: "a+".into(),
+                    flags: "gi".into()
+                }
+            ),
+            ("a".into(), JsValue::Array(vec![true.into(), "s".into()])),
+            ("h".into(), JsValue::Handle(0))
+        ]);
+        assert_eq!(round_trip(&v), v);
+        assert_ne!(round_trip(&(-0.0).into()), JsValue::Number(0.0));
+        let serialized = ser::to_value(&v).unwrap();
+        assert_eq!(serialized["value"]["o"][10]["v"], serde_json::json!({"h": 0}));
+        assert_eq!(
+            serialized["value"]["o"][8]["v"],
+            serde_json::json!({"r": {"p": "a+", "f": "gi"}})
+        );
+        let nested: Vec<Option<JsValue>> =
+            de::from_value(&serde_json::json!({"a": [{"v": "null"}, {"n": 1}]})).unwrap();
+        assert_eq!(nested, vec![None, Some(1.0.into())]);
+    }
+
+    #[test]
+    fn read_only() {
+        let raw = serde_json::json!({
+            "a": [
+                {"m": {"a": [{"a": [{"n": 1}, {"se": {"a": [{"s": "x"}]}}]}]}},
+                {"ta": {"b": "AACAPw==", "k": "f32"}},
+                {"bi": "12345678901234567890"}
+            ]
+        });
+        let v: JsValue = de::from_value(&raw).unwrap();
+        let map = JsValue::Map(vec![(1.0.into(), JsValue::Set(vec!["x".into()]))]);
+        let typed = JsValue::TypedArray {
+            kind: "f32".into(),
+            bytes: vec![0, 0, 128, 63]
+        };
+        let big = JsValue::BigInt("12345678901234567890".into());
+        assert_eq!(
+            v,
+            JsValue::Array(vec![map.clone(), typed.clone(), big.clone()])
+        );
+        assert!(ser::to_value(&map).is_err());
+        assert!(ser::to_value(&JsValue::Set(Vec::new())).is_err());
+        assert!(ser::to_value(&typed).is_err());
+        assert!(ser::to_value(&big).is_err());
+    }
+
+    #[test]
+    fn display() {
+        let v: JsValue = serde_json::from_str(r#"{"a": [1, "x"], "b-c": null}"#).unwrap();
+        assert_eq!(v.to_string(), "{ a: [1, 'x'], 'b-c': null }");
+        let v = JsValue::Array(vec![
+            JsValue::Undefined,
+            f64::NAN.into(),
+            (-0.0).into(),
+            JsValue::BigInt("1".into()),
+            2.5.into()
+        ]);
+        assert_eq!(v.to_string(), "[undefined, NaN, -0, 1n, 2.5]");
+        let v = JsValue::Map(vec![("a".into(), JsValue::Set(vec![1.0.into()]))]);
+        assert_eq!(v.to_string(), "Map(1) { 'a' => Set(1) { 1 } }");
+    }
+}
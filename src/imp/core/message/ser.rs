@@ -1,3 +1,4 @@
+use super::js_value::{HANDLE, REGEXP};
 use crate::imp::{
     core::{Guid, OnlyGuid},
     prelude::*
@@ -149,8 +150,25 @@ impl<'a> ser::Serializer for &'a mut Serializer {
         Ok(m.into())
     }
 
-    fn serialize_bytes(self, _v: &[u8]) -> Result<Self::Ok, Self::Error> {
-        Err(Error::NotSupported)
+    /// As a number. The driver has no BigInt, so values outside of `i64` and `u64` are not
+    /// supported.
+    fn serialize_i128(self, v: i128) -> Result<Self::Ok, Self::Error> {
+        match (i64::try_from(v), u64::try_from(v)) {
+            (Ok(v), _) => self.serialize_i64(v),
+            (_, Ok(v)) => self.serialize_u64(v),
+            _ => Err(Error::NotSupported)
+        }
+    }
+
+    /// As a number. The driver has no BigInt, so values outside of `u64` are not supported.
+    fn serialize_u128(self, v: u128) -> Result<Self::Ok, Self::Error> {
+        let v = u64::try_from(v).map_err(|_| Error::NotSupported)?;
+        self.serialize_u64(v)
+    }
+
+    /// As an array of numbers. The driver has no typed arrays.
+    fn serialize_bytes(self, v: &[u8]) -> Result<Self::Ok, Self::Error> {
+        ser::Serializer::collect_seq(self, v)
     }
 
     fn serialize_none(self) -> Result<Self::Ok, Self::Error> {
@@ -462,6 +480,15 @@ impl<'a> ser::SerializeStruct for &'a mut ObjectS {
             self.d = Some(d);
             return Ok(());
         }
+        if self.name == REGEXP || self.name == HANDLE {
+            // Fields of these are sent bare
+            let raw = match v {
+                Value::Object(m) => m.into_iter().next().map(|(_, v)| v),
+                _ => None
+            };
+            self.map.insert(key.into(), raw.ok_or(Error::NotSupported)?);
+            return Ok(());
+        }
         self.map.insert(key.into(), v);
         Ok(())
     }
@@ -482,6 +509,16 @@ impl<'a> ser::SerializeStruct for &'a mut ObjectS {
             let mut m = Map::new();
             m.insert("d".into(), d.into());
             Ok(m.into())
+        } else if self.name == HANDLE {
+            let mut m = Map::new();
+            mem::swap(&mut self.map, &mut m);
+            Ok(m.into())
+        } else if self.name == REGEXP {
+            let mut r = Map::new();
+            mem::swap(&mut self.map, &mut r);
+            let mut m = Map::new();
+            m.insert("r".into(), r.into());
+            Ok(m.into())
         } else {
             let mut m = Map::new();
             mem::swap(&mut self.map, &mut m);
@@ -504,7 +541,10 @@ impl<'a> ser::SerializeMap for &'a mut ObjectM {
         }
         let serialized = key.serialize(&mut self.prime)?;
         let key = match serialized {
-            Value::String(s) => s,
+            Value::Object(mut m) => match m.remove("s") {
+                Some(Value::String(s)) => s,
+                _ => return Err(Error::InvalidKey)
+            },
             _ => return Err(Error::InvalidKey)
         };
         self.keys.push(key);
@@ -532,7 +572,7 @@ impl<'a> ser::SerializeMap for &'a mut ObjectM {
         let mut vs = Vec::new();
         mem::swap(&mut self.keys, &mut ks);
         mem::swap(&mut self.values, &mut vs);
-        let entries = ks.into_iter().zip(vs.into_iter());
+        let entries = ks.into_iter().zip(vs);
         let o = convert_kv(entries);
         Ok(o.into())
     }
@@ -646,4 +686,30 @@ mod tests {
         let v: Value = serde_json::from_str(expected).unwrap();
         assert_eq!(to_value(&u).unwrap(), v);
     }
+
+    #[test]
+    fn js_types() {
+        use super::super::{Bytes, JsMap, JsSet};
+        use std::collections::BTreeMap;
+        let v = to_value(&Bytes(vec![1, 2])).unwrap();
+        assert_eq!(v["value"], serde_json::json!({"a": [{"n": 1}, {"n": 2}]}));
+        let v = to_value(&(u64::MAX as i128)).unwrap();
+        assert_eq!(v["value"], serde_json::json!({ "n": u64::MAX }));
+        assert!(to_value(&(u64::MAX as u128 + 1)).is_err());
+        let v = to_value(&JsSet(vec![1])).unwrap();
+        assert_eq!(v["value"], serde_json::json!({"a": [{"n": 1}]}));
+        let v = to_value(&JsMap(vec![(1, "a")])).unwrap();
+        assert_eq!(
+            v["value"],
+            serde_json::json!({"a": [{"a": [{"n": 1}, {"s": "a"}]}]})
+        );
+        let m: BTreeMap<i32, &str> = vec![(1, "a")].into_iter().collect();
+        assert!(matches!(to_value(&m), Err(Error::InvalidKey)));
+        let m: BTreeMap<&str, i32> = vec![("a", 1)].into_iter().collect();
+        let v = to_value(&m).unwrap();
+        assert_eq!(
+            v["value"],
+            serde_json::json!({"o": [{"k": "a", "v": {"n": 1}}]})
+        );
+    }
 }
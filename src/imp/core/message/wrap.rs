@@ -0,0 +1,163 @@
+//! Rust values for JavaScript types that serde has no shape for.
+//!
+//! The bundled driver has no wire form for `Map`, `Set` or typed arrays, so these are sent as
+//! arrays that the page turns back into the JavaScript type. Values the page returns as arrays
+//! can be read into them as well.
+use serde::{
+    de::{MapAccess, SeqAccess, Visitor},
+    Deserialize, Deserializer, Serialize, Serializer
+};
+use std::{fmt, iter::FromIterator, marker::PhantomData};
+
+/// Bytes for a `Uint8Array` in JavaScript, sent as an array of numbers.
+/// Use `new Uint8Array(x)` in the page, and `.buffer` of it for an `ArrayBuffer`.
+///
+/// Deserializes from an array of numbers as well as from a `Uint8Array`.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Bytes(pub Vec<u8>);
+
+/// Entries for a `Map` in JavaScript, with keys of any type, sent as an array of `[key, value]`.
+/// Use `new Map(x)` in the page. Entries keep their order.
+///
+/// `HashMap` and `BTreeMap` are sent as plain objects and need string keys.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct JsMap<K, V>(pub Vec<(K, V)>);
+
+/// Values for a `Set` in JavaScript, sent as an array. Use `new Set(x)` in the page.
+/// Values keep their order.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct JsSet<T>(pub Vec<T>);
+
+impl From<Vec<u8>> for Bytes {
+    fn from(x: Vec<u8>) -> Self { Self(x) }
+}
+
+impl From<&[u8]> for Bytes {
+    fn from(x: &[u8]) -> Self { Self(x.to_vec()) }
+}
+
+impl<K, V> FromIterator<(K, V)> for JsMap<K, V> {
+    fn from_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> Self {
+        Self(iter.into_iter().collect())
+    }
+}
+
+impl<T> FromIterator<T> for JsSet<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        Self(iter.into_iter().collect())
+    }
+}
+
+impl Serialize for Bytes {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer
+    {
+        serializer.serialize_bytes(&self.0)
+    }
+}
+
+impl<K: Serialize, V: Serialize> Serialize for JsMap<K, V> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer
+    {
+        serializer.collect_seq(&self.0)
+    }
+}
+
+impl<T: Serialize> Serialize for JsSet<T> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer
+    {
+        serializer.collect_seq(&self.0)
+    }
+}
+
+impl<'de> Deserialize<'de> for Bytes {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>
+    {
+        struct V;
+        impl<'de> Visitor<'de> for V {
+            type Value = Bytes;
+
+            fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result { write!(f, "bytes") }
+
+            fn visit_bytes<E>(self, v: &[u8]) -> Result<Self::Value, E> { Ok(Bytes(v.to_vec())) }
+
+            fn visit_byte_buf<E>(self, v: Vec<u8>) -> Result<Self::Value, E> { Ok(Bytes(v)) }
+
+            fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+            where
+                A: SeqAccess<'de>
+            {
+                let mut xs = Vec::new();
+                while let Some(x) = seq.next_element()? {
+                    xs.push(x);
+                }
+                Ok(Bytes(xs))
+            }
+        }
+        deserializer.deserialize_byte_buf(V)
+    }
+}
+
+impl<'de, K, V> Deserialize<'de> for JsMap<K, V>
+where
+    K: Deserialize<'de>,
+    V: Deserialize<'de>
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>
+    {
+        struct Vis<K, V>(PhantomData<(K, V)>);
+        impl<'de, K, V> Visitor<'de> for Vis<K, V>
+        where
+            K: Deserialize<'de>,
+            V: Deserialize<'de>
+        {
+            type Value = JsMap<K, V>;
+
+            fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result { write!(f, "a map") }
+
+            fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+            where
+                A: MapAccess<'de>
+            {
+                let mut entries = Vec::new();
+                while let Some(e) = map.next_entry()? {
+                    entries.push(e);
+                }
+                Ok(JsMap(entries))
+            }
+
+            fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+            where
+                A: SeqAccess<'de>
+            {
+                let mut entries = Vec::new();
+                while let Some(e) = seq.next_element()? {
+                    entries.push(e);
+                }
+                Ok(JsMap(entries))
+            }
+        }
+        deserializer.deserialize_any(Vis(PhantomData))
+    }
+}
+
+impl<'de, T> Deserialize<'de> for JsSet<T>
+where
+    T: Deserialize<'de>
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>
+    {
+        Vec::deserialize(deserializer).map(JsSet)
+    }
+}
@@ -0,0 +1,190 @@
+use crate::imp::{core::*, prelude::*};
+use std::{
+    collections::VecDeque,
+    fs::File,
+    io::{self, BufRead, BufReader, BufWriter, Write}
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub(crate) enum Direction {
+    Send,
+    Recv
+}
+
+/// One line of a recording file
+#[derive(Debug, Serialize, Deserialize)]
+struct Entry {
+    dir: Direction,
+    msg: Value
+}
+
+/// Appends every message to a file as JSON lines of `{"dir": "send" | "recv", "msg": ...}`.
+#[derive(Debug)]
+pub(crate) struct Recorder {
+    file: BufWriter<File>
+}
+
+impl Recorder {
+    pub(crate) fn create(path: &Path) -> io::Result<Self> {
+        let file = BufWriter::new(File::create(path)?);
+        Ok(Self { file })
+    }
+
+    pub(crate) fn record(&mut self, dir: Direction, bytes: &[u8]) -> Result<(), TransportError> {
+        let msg: Value = serde_json::from_slice(bytes)?;
+        serde_json::to_writer(&mut self.file, &Entry { dir, msg })?;
+        self.file.write_all(b"\n")?;
+        self.file.flush()?;
+        Ok(())
+    }
+}
+
+/// The client sent a message that does not match the recording.
+#[derive(thiserror::Error, Debug)]
+#[error(
+    "Replay diverged at message {index}: expected {}, actual {actual}",
+    expected.as_ref().map(ToString::to_string).unwrap_or_else(|| "end of recording".into())
+)]
+pub struct Divergence {
+    /// 0-based line number in the recording
+    pub index: usize,
+    pub expected: Option<Value>,
+    pub actual: Value
+}
+
+/// Serves a recorded session back.
+///
+/// Received messages are handed out until the next recorded request.
+/// Each request sent by the client must be equal to the next recorded one.
+#[derive(Debug, Default)]
+pub(crate) struct Replay {
+    entries: VecDeque<(usize, Entry)>
+}
+
+impl Replay {
+    pub(crate) fn load(path: &Path) -> Result<Self, TransportError> {
+        let file = BufReader::new(File::open(path)?);
+        let mut entries = VecDeque::new();
+        for (i, line) in file.lines().enumerate() {
+            let line = line?;
+            if line.trim().is_empty() {
+                continue;
+            }
+            entries.push_back((i, serde_json::from_str(&line)?));
+        }
+        Ok(Self { entries })
+    }
+
+    /// Framed messages received before the next request
+    pub(crate) fn read(&mut self) -> Vec<u8> {
+        let mut bytes = Vec::new();
+        while let Some((
+            _,
+            Entry {
+                dir: Direction::Recv,
+                ..
+            }
+        )) = self.entries.front()
+        {
+            let (_, e) = self.entries.pop_front().unwrap();
+            let serialized = e.msg.to_string().into_bytes();
+            bytes.extend(&(serialized.len() as u32).to_le_bytes());
+            bytes.extend(serialized);
+        }
+        bytes
+    }
+
+    /// Every recorded message has been served
+    pub(crate) fn is_finished(&self) -> bool { self.entries.is_empty() }
+
+    pub(crate) fn write(&mut self, bytes: &[u8]) -> Result<(), TransportError> {
+        let actual: Value = serde_json::from_slice(bytes)?;
+        let pos = self
+            .entries
+            .iter()
+            .position(|(_, e)| e.dir == Direction::Send);
+        let (index, expected) = match pos.and_then(|p| self.entries.remove(p)) {
+            Some((i, e)) => (i, e.msg),
+            None => {
+                return Err(Divergence {
+                    index: self.entries.back().map(|(i, _)| i + 1).unwrap_or_default(),
+                    expected: None,
+                    actual
+                }
+                .into())
+            }
+        };
+        if expected != actual {
+            return Err(Divergence {
+                index,
+                expected: Some(expected),
+                actual
+            }
+            .into());
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn replay(lines: &[&str]) -> Replay {
+        let entries = lines
+            .iter()
+            .enumerate()
+            .map(|(i, l)| (i, serde_json::from_str(l).unwrap()))
+            .collect();
+        Replay { entries }
+    }
+
+    #[test]
+    fn serve_until_request() {
+        let mut r = replay(&[
+            r#"{"dir":"recv","msg":{"guid":"","method":"__create__"}}"#,
+            r#"{"dir":"send","msg":{"id":1,"guid":"a","method":"m","params":{}}}"#,
+            r#"{"dir":"recv","msg":{"id":1,"result":{}}}"#
+        ]);
+        let first = r.read();
+        assert_eq!(&first[4..], br#"{"guid":"","method":"__create__"}"#);
+        assert!(r.read().is_empty());
+        r.write(br#"{"id":1,"guid":"a","method":"m","params":{}}"#)
+            .unwrap();
+        assert!(!r.read().is_empty());
+        assert!(r.is_finished());
+    }
+
+    #[test]
+    fn end_of_recording() {
+        let r = replay(&[r#"{"dir":"recv","msg":{"guid":"","method":"__create__","params":{}}}"#]);
+        let mut reader = crate::imp::core::transport::Reader::replay(Arc::new(Mutex::new(r)));
+        let first = loop {
+            if let Some(res) = reader.try_read().unwrap() {
+                break res;
+            }
+        };
+        assert!(matches!(first, Res::Initial(_)));
+        let err = reader.try_read().unwrap_err();
+        assert!(matches!(err, TransportError::Io(e) if e.kind() == io::ErrorKind::UnexpectedEof));
+    }
+
+    #[test]
+    fn divergence() {
+        let mut r =
+            replay(&[r#"{"dir":"send","msg":{"id":1,"guid":"a","method":"m","params":{}}}"#]);
+        let err = r
+            .write(br#"{"id":1,"guid":"a","method":"other","params":{}}"#)
+            .unwrap_err();
+        assert!(matches!(
+            err,
+            TransportError::Divergence(Divergence { index: 0, .. })
+        ));
+        let err = r.write(br#"{"id":2}"#).unwrap_err();
+        assert!(matches!(
+            err,
+            TransportError::Divergence(Divergence { expected: None, .. })
+        ));
+    }
+}
@@ -6,6 +6,72 @@ pub(crate) fn upgrade<T>(w: &Weak<T>) -> Result<Arc<T>, Error> {
     w.upgrade().ok_or(Error::ObjectNotFound)
 }
 
+/// Runs the future in the background. Outside of a runtime, for example when a handle is dropped
+/// after the caller's runtime has shut down, it runs on a runtime thread kept for this.
+pub(crate) fn spawn_detached<F>(f: F)
+where
+    F: Future<Output = ()> + Send + 'static
+{
+    #[cfg(any(feature = "rt-tokio", feature = "rt-actix"))]
+    match tokio::runtime::Handle::try_current() {
+        Ok(handle) => {
+            handle.spawn(f);
+        }
+        Err(_) => match fallback_runtime() {
+            Some(handle) => {
+                handle.spawn(f);
+            }
+            None => log::warn!("No runtime to run a background task on, it is skipped")
+        }
+    }
+    #[cfg(feature = "rt-async-std")]
+    async_std::task::spawn(f);
+}
+
+#[cfg(any(feature = "rt-tokio", feature = "rt-actix"))]
+fn fallback_runtime() -> Option<&'static tokio::runtime::Handle> {
+    static HANDLE: std::sync::OnceLock<Option<tokio::runtime::Handle>> = std::sync::OnceLock::new();
+    HANDLE
+        .get_or_init(|| {
+            let rt = tokio::runtime::Builder::new_current_thread()
+                .enable_all()
+                .build();
+            let rt = match rt {
+                Ok(rt) => rt,
+                Err(e) => {
+                    log::warn!("Failed to start the background runtime: {}", e);
+                    return None;
+                }
+            };
+            let handle = rt.handle().clone();
+            std::thread::Builder::new()
+                .name("playwright-background".into())
+                .spawn(move || rt.block_on(futures::future::pending::<()>()))
+                .map_err(|e| log::warn!("Failed to start the background runtime: {}", e))
+                .ok()?;
+            Some(handle)
+        })
+        .as_ref()
+}
+
+/// Fails with [`Error::Timeout`] unless `f` completes within `deadline`
+pub(crate) async fn with_deadline<F, T>(deadline: Option<Duration>, f: F) -> ArcResult<T>
+where
+    F: Future<Output = ArcResult<T>>
+{
+    let deadline = match deadline {
+        Some(d) => d,
+        None => return f.await
+    };
+    let timer = sleep(deadline);
+    futures::pin_mut!(f);
+    futures::pin_mut!(timer);
+    match futures::future::select(f, timer).await {
+        futures::future::Either::Left((x, _)) => x,
+        futures::future::Either::Right(_) => Err(Arc::new(Error::Timeout))
+    }
+}
+
 pub(crate) fn weak_and_then<T, U, F>(w: &Weak<T>, f: F) -> Weak<U>
 where
     F: FnOnce(Arc<T>) -> Weak<U>
@@ -60,15 +126,28 @@ impl ChannelOwner {
         RequestBody::new(self.guid.clone(), method)
     }
 
-    pub(crate) async fn send_message(
-        &self,
-        r: RequestBody
-    ) -> Result<WaitData<WaitMessageResult>, Error> {
-        let wait = WaitData::new();
-        let r = r.set_wait(&wait);
-        let ctx = upgrade(&self.ctx)?;
-        ctx.lock().unwrap().send_message(r)?;
-        Ok(wait)
+    /// Sends a request and waits for its response.
+    /// Dropping the future before the response arrives forgets the request.
+    pub(crate) async fn request(&self, r: RequestBody) -> WaitMessageResult {
+        #[cfg(feature = "tracing")]
+        let method = r.method.clone();
+        let fut = async move {
+            let wait = WaitData::new();
+            let r = r.set_wait(&wait);
+            let ctx = upgrade(&self.ctx)?;
+            let (id, deadline) = {
+                let mut ctx = ctx.lock().unwrap();
+                (ctx.send_message(r)?, ctx.default_deadline())
+            };
+            let _guard = CallbackGuard {
+                ctx: Arc::downgrade(&ctx),
+                id
+            };
+            with_deadline(deadline, wait).await
+        };
+        #[cfg(feature = "tracing")]
+        let fut = trace::request(self, &method, fut);
+        fut.await
     }
 
     pub(crate) fn children(&self) -> Vec<RemoteWeak> { self.children.lock().unwrap().to_vec() }
@@ -77,6 +156,31 @@ impl ChannelOwner {
         let children = &mut self.children.lock().unwrap();
         children.push(c);
     }
+
+    /// Removes the child and children already released
+    pub(crate) fn remove_child(&self, guid: &S<Guid>) {
+        let children = &mut self.children.lock().unwrap();
+        children.retain(|c| match c.upgrade() {
+            Some(c) => c.channel().guid != *guid,
+            None => false
+        });
+    }
+}
+
+/// Removes the callback of a request when its response arrives or its future is dropped
+struct CallbackGuard {
+    ctx: Wm<Context>,
+    id: i32
+}
+
+impl Drop for CallbackGuard {
+    fn drop(&mut self) {
+        if let Some(ctx) = self.ctx.upgrade() {
+            if let Ok(mut ctx) = ctx.lock() {
+                ctx.remove_callback(self.id);
+            }
+        }
+    }
 }
 
 #[derive(Debug)]
@@ -0,0 +1,48 @@
+use crate::imp::{core::*, prelude::*};
+use std::time::Instant;
+use tracing::{field, Instrument, Span};
+
+/// Runs a request inside a span with guid, object type, method and id
+/// and records the round-trip time and result status on completion.
+pub(crate) async fn request<F>(channel: &ChannelOwner, method: &S<Method>, fut: F) -> WaitMessageResult
+where
+    F: Future<Output = WaitMessageResult>
+{
+    let span = tracing::debug_span!(
+        "request",
+        guid = channel.guid.as_str(),
+        object_type = channel.typ.as_str(),
+        method = method.as_str(),
+        id = field::Empty,
+        elapsed_ms = field::Empty,
+        status = field::Empty
+    );
+    let start = Instant::now();
+    let res = fut.instrument(span.clone()).await;
+    let elapsed = start.elapsed().as_secs_f64() * 1000.;
+    span.record("elapsed_ms", &elapsed);
+    span.record("status", &status(&res));
+    tracing::debug!(parent: &span, elapsed_ms = elapsed, status = status(&res), "response");
+    res
+}
+
+/// Called by [`Context`] while the request span is current.
+pub(crate) fn record_id(id: i32) { Span::current().record("id", &id); }
+
+/// Span for an event dispatched to a remote object.
+pub(crate) fn event(channel: &ChannelOwner, method: &S<Method>) -> Span {
+    tracing::debug_span!(
+        "event",
+        guid = channel.guid.as_str(),
+        object_type = channel.typ.as_str(),
+        method = method.as_str()
+    )
+}
+
+fn status(res: &WaitMessageResult) -> &'static str {
+    match res {
+        Ok(Ok(_)) => "ok",
+        Ok(Err(_)) => "error",
+        Err(_) => "failed"
+    }
+}
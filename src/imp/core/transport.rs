@@ -1,4 +1,4 @@
-use crate::imp::core::*;
+use crate::imp::{core::*, prelude::*};
 use std::{
     convert::TryInto,
     io,
@@ -9,14 +9,28 @@ use thiserror::Error;
 
 #[derive(Debug)]
 pub(super) struct Reader {
-    stdout: ChildStdout,
+    stdout: Source,
     length: Option<u32>,
-    buf: Vec<u8>
+    buf: Vec<u8>,
+    recorder: Option<Am<Recorder>>
 }
 
 #[derive(Debug)]
 pub(super) struct Writer {
-    stdin: ChildStdin
+    stdin: Sink,
+    recorder: Option<Am<Recorder>>
+}
+
+#[derive(Debug)]
+enum Source {
+    Driver(ChildStdout),
+    Replay(Am<Replay>)
+}
+
+#[derive(Debug)]
+enum Sink {
+    Driver(ChildStdin),
+    Replay(Am<Replay>)
 }
 
 #[derive(Error, Debug)]
@@ -24,20 +38,31 @@ pub enum TransportError {
     #[error(transparent)]
     Serde(#[from] serde_json::error::Error),
     #[error(transparent)]
-    Io(#[from] io::Error)
+    Io(#[from] io::Error),
+    #[error(transparent)]
+    Divergence(#[from] Divergence)
 }
 
 impl Reader {
     const BUFSIZE: usize = 30000;
 
-    pub(super) fn new(stdout: ChildStdout) -> Self {
+    pub(super) fn new(stdout: ChildStdout) -> Self { Self::with_source(Source::Driver(stdout)) }
+
+    pub(super) fn replay(replay: Am<Replay>) -> Self { Self::with_source(Source::Replay(replay)) }
+
+    fn with_source(stdout: Source) -> Self {
         Self {
             stdout,
             length: None,
-            buf: Vec::with_capacity(Self::BUFSIZE)
+            buf: Vec::with_capacity(Self::BUFSIZE),
+            recorder: None
         }
     }
 
+    pub(super) fn set_recorder(&mut self, recorder: Am<Recorder>) {
+        self.recorder = Some(recorder);
+    }
+
     // TODO: heap efficiency
     pub(super) fn try_read(&mut self) -> Result<Option<Res>, TransportError> {
         let this = self;
@@ -54,6 +79,9 @@ impl Reader {
                 Some(l) => {
                     let bytes: &[u8] = &this.buf[..l];
                     log::debug!("RECV {}", unsafe { std::str::from_utf8_unchecked(bytes) });
+                    if let Some(r) = &this.recorder {
+                        r.lock().unwrap().record(Direction::Recv, bytes)?;
+                    }
                     let msg: Res = serde_json::from_slice(bytes)?;
                     this.length = None;
                     this.buf = this.buf[l..].to_owned();
@@ -61,25 +89,63 @@ impl Reader {
                 }
             }
         }
-        {
-            let mut buf = [0; Self::BUFSIZE];
-            let n = this.stdout.read(&mut buf)?;
-            this.buf.extend(&buf[..n]);
+        match &mut this.stdout {
+            Source::Driver(stdout) => {
+                let mut buf = [0; Self::BUFSIZE];
+                let n = stdout.read(&mut buf)?;
+                this.buf.extend(&buf[..n]);
+            }
+            Source::Replay(replay) => {
+                let mut replay = replay.lock().unwrap();
+                let bytes = replay.read();
+                if bytes.is_empty() {
+                    if replay.is_finished() && this.buf.is_empty() {
+                        let eof = io::Error::new(io::ErrorKind::UnexpectedEof, "end of recording");
+                        return Err(eof.into());
+                    }
+                    std::thread::sleep(Duration::from_millis(1));
+                }
+                this.buf.extend(bytes);
+            }
         }
         Ok(None)
     }
 }
 
 impl Writer {
-    pub(super) fn new(stdin: ChildStdin) -> Self { Self { stdin } }
+    pub(super) fn new(stdin: ChildStdin) -> Self {
+        Self {
+            stdin: Sink::Driver(stdin),
+            recorder: None
+        }
+    }
+
+    pub(super) fn replay(replay: Am<Replay>) -> Self {
+        Self {
+            stdin: Sink::Replay(replay),
+            recorder: None
+        }
+    }
+
+    pub(super) fn set_recorder(&mut self, recorder: Am<Recorder>) {
+        self.recorder = Some(recorder);
+    }
 
     pub(super) fn send(&mut self, req: &Req<'_, '_>) -> Result<(), TransportError> {
         log::debug!("SEND {:?}", &req);
         let serialized = serde_json::to_vec(&req)?;
-        let length = serialized.len() as u32;
-        let mut bytes = length.to_le_bytes().to_vec();
-        bytes.extend(serialized);
-        self.stdin.write_all(&bytes)?;
+        if let Some(r) = &self.recorder {
+            r.lock().unwrap().record(Direction::Send, &serialized)?;
+        }
+        match &mut self.stdin {
+            Sink::Driver(stdin) => {
+                let length = serialized.len() as u32;
+                let mut bytes = length.to_le_bytes().to_vec();
+                bytes.extend(serialized);
+                stdin.write_all(&bytes)?;
+            }
+            Sink::Replay(replay) => replay.lock().unwrap().write(&serialized)?
+        }
         Ok(())
     }
 }
@@ -1,16 +1,19 @@
 use crate::imp::{
     core::*,
     frame::Frame,
+    js_handle,
     prelude::*,
     utils::{
         ElementState, File, FloatRect, KeyboardModifier, MouseButton, Position, ScreenshotType,
-        WaitForSelectorState
+        ScrollAlignment, ScrollBehavior, WaitForSelectorState
     }
 };
+use std::fmt;
 
 #[derive(Debug)]
 pub(crate) struct ElementHandle {
-    channel: ChannelOwner
+    channel: ChannelOwner,
+    preview: Mutex<String>
 }
 
 macro_rules! is_checked {
@@ -27,7 +30,18 @@ macro_rules! is_checked {
 }
 
 impl ElementHandle {
-    pub(crate) fn new(channel: ChannelOwner) -> Self { Self { channel } }
+    pub(crate) fn new(channel: ChannelOwner) -> Self {
+        let preview = channel
+            .initializer
+            .get("preview")
+            .and_then(Value::as_str)
+            .unwrap_or_default()
+            .to_owned();
+        Self {
+            channel,
+            preview: Mutex::new(preview)
+        }
+    }
 
     pub(crate) async fn query_selector(
         &self,
@@ -151,6 +165,11 @@ impl ElementHandle {
         Ok(())
     }
 
+    pub(crate) async fn dispose(&self) -> ArcResult<()> {
+        let _ = send_message!(self, "dispose", Map::new());
+        Ok(())
+    }
+
     pub(crate) async fn focus(&self) -> ArcResult<()> {
         let _ = send_message!(self, "focus", Map::new());
         Ok(())
@@ -178,6 +197,12 @@ impl ElementHandle {
         Ok(())
     }
 
+    pub(crate) async fn scroll_into_view(&self, args: ScrollIntoViewArgs) -> ArcResult<()> {
+        let expression = "(e, options) => e.scrollIntoView(options)";
+        let _: Value = js_handle::evaluate(self, expression, Some(args)).await?;
+        Ok(())
+    }
+
     pub(crate) async fn select_text(&self, timeout: Option<f64>) -> ArcResult<()> {
         #[skip_serializing_none]
         #[derive(Serialize)]
@@ -301,7 +326,7 @@ pub(crate) struct HoverArgs {
 }
 
 #[skip_serializing_none]
-#[derive(Serialize, Default)]
+#[derive(Serialize, Default, Clone)]
 #[serde(rename_all = "camelCase")]
 pub(crate) struct ClickArgs {
     pub(crate) modifiers: Option<Vec<KeyboardModifier>>,
@@ -340,7 +365,7 @@ pub(crate) struct TapArgs {
 }
 
 #[skip_serializing_none]
-#[derive(Serialize)]
+#[derive(Serialize, Clone)]
 #[serde(rename_all = "camelCase")]
 pub(crate) struct FillArgs<'a> {
     value: &'a str,
@@ -364,14 +389,14 @@ macro_rules! type_args {
         #[derive(Serialize)]
         #[serde(rename_all = "camelCase")]
         pub(crate) struct $t<'a> {
-            $f: &'a str,
+            $f: Cow<'a, str>,
             pub(crate) delay: Option<f64>,
             pub(crate) timeout: Option<f64>,
             pub(crate) no_wait_after: Option<bool>
         }
 
         impl<'a> $t<'a> {
-            pub(crate) fn new($f: &'a str) -> Self {
+            pub(crate) fn new($f: Cow<'a, str>) -> Self {
                 Self {
                     $f,
                     delay: None,
@@ -419,6 +444,36 @@ impl<'a> WaitForSelectorArgs<'a> {
 impl RemoteObject for ElementHandle {
     fn channel(&self) -> &ChannelOwner { &self.channel }
     fn channel_mut(&mut self) -> &mut ChannelOwner { &mut self.channel }
+
+    fn handle_event(
+        &self,
+        _ctx: &Context,
+        method: Str<Method>,
+        params: Map<String, Value>
+    ) -> Result<(), Error> {
+        if method.as_str() == "previewUpdated" {
+            if let Some(p) = params.get("preview").and_then(Value::as_str) {
+                *self.preview.lock().unwrap() = p.to_owned();
+            }
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for ElementHandle {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", &self.preview.lock().unwrap())
+    }
+}
+
+/// Options of `Element.scrollIntoView`
+#[skip_serializing_none]
+#[derive(Serialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct ScrollIntoViewArgs {
+    pub(crate) block: Option<ScrollAlignment>,
+    pub(crate) inline: Option<ScrollAlignment>,
+    pub(crate) behavior: Option<ScrollBehavior>
 }
 
 #[skip_serializing_none]
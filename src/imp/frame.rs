@@ -2,7 +2,7 @@ pub(crate) use crate::imp::element_handle::Opt;
 use crate::imp::{
     core::*,
     element_handle::ElementHandle,
-    js_handle::JsHandle,
+    js_handle::{Handle, JsHandle},
     page::Page,
     prelude::*,
     response::Response,
@@ -24,7 +24,8 @@ struct Variable {
     name: String,
     page: Option<Weak<Page>>,
     child_frames: Vec<Weak<Frame>>,
-    load_states: HashSet<DocumentLoadState>
+    load_states: HashSet<DocumentLoadState>,
+    detached: bool
 }
 
 macro_rules! is_checked {
@@ -82,7 +83,8 @@ impl Frame {
             name,
             page: None,
             child_frames: Vec::new(),
-            load_states: HashSet::from_iter(load_states)
+            load_states: HashSet::from_iter(load_states),
+            detached: false
         });
         Ok(Self {
             channel,
@@ -360,13 +362,7 @@ impl Frame {
         let args = Args { expression, arg };
         let v = send_message!(self, "evaluateExpressionHandle", args);
         let guid = only_guid(&v)?;
-        let e = get_object!(self.context()?.lock().unwrap(), guid, ElementHandle)
-            .ok()
-            .map(Handle::Element);
-        let j = get_object!(self.context()?.lock().unwrap(), guid, JsHandle)
-            .ok()
-            .map(Handle::Js);
-        let h = e.or(j).ok_or(Error::ObjectNotFound)?;
+        let h = Handle::find(&self.context()?.lock().unwrap(), guid)?;
         Ok(h)
     }
 
@@ -504,6 +500,21 @@ impl Frame {
         self.var.lock().unwrap().child_frames.push(child);
     }
 
+    pub(crate) fn is_detached(&self) -> bool { self.var.lock().unwrap().detached }
+
+    /// Marks this frame detached and removes it from the parent's children
+    pub(crate) fn on_detached(&self) {
+        self.var.lock().unwrap().detached = true;
+        if let Some(parent) = self.parent_frame.as_ref().and_then(Weak::upgrade) {
+            parent
+                .var
+                .lock()
+                .unwrap()
+                .child_frames
+                .retain(|c| c.upgrade().map(|c| c.guid() != self.guid()).unwrap_or(false));
+        }
+    }
+
     fn on_navigated(&self, ctx: &Context, params: Map<String, Value>) -> Result<(), Error> {
         let var = &mut self.var.lock().unwrap();
         let payload: FrameNavigatedEvent = serde_json::from_value(params.into())?;
@@ -591,11 +602,6 @@ impl IsEvent for Evt {
     }
 }
 
-enum Handle {
-    Js(Weak<JsHandle>),
-    Element(Weak<ElementHandle>)
-}
-
 #[skip_serializing_none]
 #[derive(Serialize)]
 #[serde(rename_all = "camelCase")]
@@ -686,14 +692,14 @@ macro_rules! type_args {
         #[serde(rename_all = "camelCase")]
         pub(crate) struct $t<'a, 'b> {
             selector: &'a str,
-            $f: &'b str,
+            $f: Cow<'b, str>,
             pub(crate) delay: Option<f64>,
             pub(crate) timeout: Option<f64>,
             pub(crate) no_wait_after: Option<bool>
         }
 
         impl<'a, 'b> $t<'a, 'b> {
-            pub(crate) fn new(selector: &'a str, $f: &'b str) -> Self {
+            pub(crate) fn new(selector: &'a str, $f: Cow<'b, str>) -> Self {
                 Self {
                     selector,
                     $f,
@@ -1,4 +1,4 @@
-use crate::imp::{core::*, prelude::*};
+use crate::imp::{core::*, element_handle::ElementHandle, prelude::*};
 use std::fmt;
 
 #[derive(Debug)]
@@ -19,49 +19,107 @@ impl JsHandle {
         Ok(Self { channel, var })
     }
 
-    pub(crate) async fn get_property(&self, name: &str) -> ArcResult<Weak<JsHandle>> {
-        let mut args = HashMap::new();
-        args.insert("name", name);
-        let v = send_message!(self, "getProperty", args);
-        let guid = only_guid(&v)?;
-        let j = get_object!(self.context()?.lock().unwrap(), guid, JsHandle)?;
-        Ok(j)
-    }
-
-    pub(crate) async fn get_properties(&self) -> ArcResult<HashMap<String, Weak<JsHandle>>> {
-        let v = send_message!(self, "getPropertyList", Map::new());
-        let first = first(&v).ok_or(Error::InvalidParams)?;
-        let properties: Vec<Property> =
-            serde_json::from_value((*first).clone()).map_err(Error::Serde)?;
-        let ps = properties
-            .into_iter()
-            .map(
-                |Property {
-                     name,
-                     value: OnlyGuid { guid }
-                 }| {
-                    get_object!(self.context()?.lock().unwrap(), &guid, JsHandle).map(|o| (name, o))
-                }
-            )
-            .collect::<Result<HashMap<_, _>, Error>>()?;
-        Ok(ps)
-    }
-
     pub(crate) async fn dispose(&self) -> ArcResult<()> {
         let _ = send_message!(self, "dispose", Map::new());
         Ok(())
     }
+}
 
-    pub(crate) async fn json_value<U>(&self) -> ArcResult<U>
-    where
-        U: DeserializeOwned
-    {
-        let v = send_message!(self, "jsonValue", Map::new());
-        let first = first(&v).ok_or(Error::ObjectNotFound)?;
-        Ok(de::from_value(first).map_err(Error::DeserializationPwJson)?)
+/// Either kind of handle. Both implement the `JSHandle` interface of the protocol.
+#[derive(Debug, Clone)]
+pub(crate) enum Handle {
+    Js(Weak<JsHandle>),
+    Element(Weak<ElementHandle>)
+}
+
+impl Handle {
+    pub(crate) fn find(ctx: &Context, guid: &S<Guid>) -> Result<Self, Error> {
+        match ctx.find_object(guid) {
+            Some(RemoteWeak::JsHandle(x)) => Ok(Self::Js(x)),
+            Some(RemoteWeak::ElementHandle(x)) => Ok(Self::Element(x)),
+            _ => Err(Error::ObjectNotFound)
+        }
     }
 }
 
+// Methods of the `JSHandle` interface, for `JsHandle` and `ElementHandle`
+
+pub(crate) async fn get_property<R: RemoteObject>(r: &R, name: &str) -> ArcResult<Handle> {
+    let mut args = HashMap::new();
+    args.insert("name", name);
+    let v = send_message!(r, "getProperty", args);
+    let guid = only_guid(&v)?;
+    let h = Handle::find(&r.context()?.lock().unwrap(), guid)?;
+    Ok(h)
+}
+
+pub(crate) async fn get_properties<R: RemoteObject>(r: &R) -> ArcResult<HashMap<String, Handle>> {
+    let v = send_message!(r, "getPropertyList", Map::new());
+    let first = first(&v).ok_or(Error::InvalidParams)?;
+    let properties: Vec<Property> =
+        serde_json::from_value((*first).clone()).map_err(Error::Serde)?;
+    let ctx = r.context()?;
+    let ctx = ctx.lock().unwrap();
+    let ps = properties
+        .into_iter()
+        .map(
+            |Property {
+                 name,
+                 value: OnlyGuid { guid }
+             }| Handle::find(&ctx, &guid).map(|h| (name, h))
+        )
+        .collect::<Result<HashMap<_, _>, Error>>()?;
+    Ok(ps)
+}
+
+pub(crate) async fn json_value<R, U>(r: &R) -> ArcResult<U>
+where
+    R: RemoteObject,
+    U: DeserializeOwned
+{
+    let v = send_message!(r, "jsonValue", Map::new());
+    let first = first(&v).ok_or(Error::ObjectNotFound)?;
+    Ok(de::from_value(first).map_err(Error::DeserializationPwJson)?)
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct EvaluateArgs<'a> {
+    expression: &'a str,
+    arg: Value
+}
+
+/// Evaluates `expression` with the handle as the first argument
+pub(crate) async fn evaluate<R, T, U>(r: &R, expression: &str, arg: Option<T>) -> ArcResult<U>
+where
+    R: RemoteObject,
+    T: Serialize,
+    U: DeserializeOwned
+{
+    let arg = ser::to_value(&arg).map_err(Error::SerializationPwJson)?;
+    let args = EvaluateArgs { expression, arg };
+    let v = send_message!(r, "evaluateExpression", args);
+    let first = first(&v).ok_or(Error::ObjectNotFound)?;
+    Ok(de::from_value(first).map_err(Error::DeserializationPwJson)?)
+}
+
+pub(crate) async fn evaluate_handle<R, T>(
+    r: &R,
+    expression: &str,
+    arg: Option<T>
+) -> ArcResult<Handle>
+where
+    R: RemoteObject,
+    T: Serialize
+{
+    let arg = ser::to_value(&arg).map_err(Error::SerializationPwJson)?;
+    let args = EvaluateArgs { expression, arg };
+    let v = send_message!(r, "evaluateExpressionHandle", args);
+    let guid = only_guid(&v)?;
+    let h = Handle::find(&r.context()?.lock().unwrap(), guid)?;
+    Ok(h)
+}
+
 impl JsHandle {
     fn set_preview(&self, preview: String) {
         let var = &mut self.var.lock().unwrap();
@@ -0,0 +1,147 @@
+//! Forwards browser-side output into the `log` facade.
+//!
+//! Targets are `playwright::page::<guid>`, `playwright::worker::<guid>` and `playwright::driver`.
+//! `tracing` subscribers receive these records through `tracing-log`.
+use crate::imp::{console_message::ConsoleMessage, core::*, prelude::*, utils::SourceLocation};
+use log::Level;
+use std::{
+    io::{BufRead, BufReader},
+    process::ChildStderr
+};
+
+pub(crate) const DRIVER_TARGET: &str = "playwright::driver";
+
+pub(crate) fn page_target(guid: &S<Guid>) -> String {
+    format!("playwright::page::{}", guid.as_str())
+}
+
+pub(crate) fn worker_target(guid: &S<Guid>) -> String {
+    format!("playwright::worker::{}", guid.as_str())
+}
+
+fn console_level(typ: &str) -> Level {
+    match typ {
+        "error" | "assert" => Level::Error,
+        "warning" => Level::Warn,
+        "debug" => Level::Debug,
+        "trace" => Level::Trace,
+        _ => Level::Info
+    }
+}
+
+pub(crate) fn console(target: &str, message: &ConsoleMessage) {
+    let level = console_level(message.r#type());
+    if !log::log_enabled!(target: target, level) {
+        return;
+    }
+    log::log!(
+        target: target,
+        level,
+        "{}",
+        console_line(message.text(), message.location())
+    );
+}
+
+fn console_line(text: &str, loc: &SourceLocation) -> String {
+    format!(
+        "{} ({}:{}:{})",
+        text, loc.url, loc.line_number, loc.column_number
+    )
+}
+
+#[derive(Debug, Deserialize)]
+struct SerializedError {
+    error: Option<ErrorMessage>,
+    value: Option<Value>
+}
+
+/// Logs the `pageError` event params. A payload that does not parse is logged as a warning so
+/// that forwarding never breaks event dispatch.
+pub(crate) fn page_error(target: &str, params: &Map<String, Value>) {
+    match parse_page_error(params) {
+        Ok(error) => log::error!(target: target, "{}", page_error_line(&error)),
+        Err(e) => log::warn!(target: target, "Unrecognized pageError {:?}: {}", params, e)
+    }
+}
+
+fn parse_page_error(params: &Map<String, Value>) -> Result<SerializedError, serde_json::Error> {
+    #[derive(Deserialize)]
+    struct De {
+        error: SerializedError
+    }
+    let De { error } = serde_json::from_value(params.clone().into())?;
+    Ok(error)
+}
+
+fn page_error_line(error: &SerializedError) -> String {
+    match error {
+        SerializedError { error: Some(e), .. } => format!("{}: {}\n{}", e.name, e.message, e.stack),
+        SerializedError { value: Some(v), .. } => format!("Uncaught {}", v),
+        _ => "Uncaught error".into()
+    }
+}
+
+/// Reads driver stderr line by line until the driver exits.
+pub(crate) fn spawn_driver_stderr(stderr: ChildStderr) {
+    std::thread::spawn(move || {
+        forward_lines(
+            BufReader::new(stderr),
+            |l| log::debug!(target: DRIVER_TARGET, "{}", l)
+        )
+    });
+}
+
+fn forward_lines<R: BufRead>(reader: R, mut f: impl FnMut(&str)) {
+    for line in reader.lines() {
+        match line {
+            Ok(l) => f(&l),
+            Err(_) => break
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn level() {
+        assert_eq!(console_level("error"), Level::Error);
+        assert_eq!(console_level("warning"), Level::Warn);
+        assert_eq!(console_level("log"), Level::Info);
+        assert_eq!(console_level("debug"), Level::Debug);
+    }
+
+    #[test]
+    fn console() {
+        let loc = SourceLocation {
+            url: "https://a.com/app.js".into(),
+            line_number: 3,
+            column_number: 14
+        };
+        assert_eq!(console_line("hi", &loc), "hi (https://a.com/app.js:3:14)");
+    }
+
+    #[test]
+    fn page_error() {
+        let params = |v: Value| v.as_object().unwrap().clone();
+        let thrown = params(serde_json::json!({
+            "error": {"error": {"name": "TypeError", "message": "x is null", "stack": "at f"}}
+        }));
+        let e = parse_page_error(&thrown).unwrap();
+        assert_eq!(page_error_line(&e), "TypeError: x is null\nat f");
+        let value = params(serde_json::json!({"error": {"value": {"s": "oops"}}}));
+        let e = parse_page_error(&value).unwrap();
+        assert!(page_error_line(&e).starts_with("Uncaught "));
+        let unknown = params(serde_json::json!({"error": 1}));
+        assert!(parse_page_error(&unknown).is_err());
+        super::page_error("playwright::page::test", &unknown);
+    }
+
+    #[test]
+    fn driver_lines() {
+        let mut lines = Vec::new();
+        forward_lines(&b"pw:api a\npw:api b\n"[..], |l| lines.push(l.to_owned()));
+        assert_eq!(lines, ["pw:api a", "pw:api b"]);
+    }
+}
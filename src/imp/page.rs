@@ -10,8 +10,8 @@ use crate::imp::{
     request::Request,
     response::Response,
     utils::{
-        ColorScheme, DocumentLoadState, FloatRect, Header, Length, MouseButton, PdfMargins,
-        ScreenshotType, Viewport
+        check_page_ranges, ColorScheme, DocumentLoadState, FloatRect, Header, Length, Margin,
+        MouseButton, PaperFormat, Position, ScreenshotType, Viewport
     },
     video::Video,
     websocket::WebSocket,
@@ -34,7 +34,9 @@ pub(crate) struct Variable {
     timeout: Option<u32>,
     navigation_timeout: Option<u32>,
     workers: Vec<Weak<Worker>>,
-    video: Option<Video>
+    video: Option<Video>,
+    /// Where the mouse was last moved to by `Mouse`. Element actions are not tracked.
+    mouse: (f64, f64)
 }
 
 macro_rules! navigation {
@@ -152,15 +154,15 @@ impl Page {
         Ok(())
     }
 
-    pub(crate) async fn key_press(&self, text: &str, delay: Option<f64>) -> Result<(), Arc<Error>> {
+    pub(crate) async fn key_press(&self, key: &str, delay: Option<f64>) -> Result<(), Arc<Error>> {
         #[skip_serializing_none]
         #[derive(Serialize)]
         #[serde(rename_all = "camelCase")]
         struct Args<'b> {
-            text: &'b str,
+            key: &'b str,
             delay: Option<f64>
         }
-        let args = Args { text, delay };
+        let args = Args { key, delay };
         let _ = send_message!(self, "keyboardPress", args);
         Ok(())
     }
@@ -193,6 +195,34 @@ impl Page {
         }
         let args = Args { x, y, steps };
         let _ = send_message!(self, "mouseMove", args);
+        self.var.lock().unwrap().mouse = (x, y);
+        Ok(())
+    }
+
+    pub(crate) fn mouse_position(&self) -> (f64, f64) { self.var.lock().unwrap().mouse }
+
+    /// Dispatched from the page, as the driver has no touch input other than tap
+    pub(crate) async fn touch_gesture(&self, args: TouchGestureArgs) -> ArcResult<()> {
+        let frame = upgrade(&self.main_frame)?;
+        let _: Value = frame.evaluate(GESTURE, Some(args)).await?;
+        Ok(())
+    }
+
+    /// Dispatched from the page, as the driver has no wheel input
+    pub(crate) async fn mouse_wheel(&self, delta_x: f64, delta_y: f64) -> ArcResult<()> {
+        let (x, y) = self.mouse_position();
+        let frame = upgrade(&self.main_frame)?;
+        let _: Value = frame
+            .evaluate(WHEEL, Some((x, y, delta_x, delta_y)))
+            .await?;
+        Ok(())
+    }
+
+    pub(crate) async fn scroll_by(&self, x: f64, y: f64) -> ArcResult<()> {
+        let frame = upgrade(&self.main_frame)?;
+        let _: Value = frame
+            .evaluate("([x, y]) => window.scrollBy(x, y)", Some((x, y)))
+            .await?;
         Ok(())
     }
 
@@ -200,7 +230,9 @@ impl Page {
     mouse_down! {mouse_up, "mouseUp"}
 
     pub(crate) async fn mouse_click(&self, args: MouseClickArgs) -> Result<(), Arc<Error>> {
+        let position = (args.x, args.y);
         let _ = send_message!(self, "mouseClick", args);
+        self.var.lock().unwrap().mouse = position;
         Ok(())
     }
 
@@ -238,11 +270,11 @@ impl Page {
         Ok(())
     }
 
-    pub(crate) async fn pdf(
-        &self,
-        args: PdfArgs<'_, '_, '_, '_, '_, '_, '_, '_, '_, '_>
-    ) -> ArcResult<Vec<u8>> {
-        let path = args.path.clone();
+    pub(crate) async fn pdf(&self, args: PdfArgs) -> ArcResult<Vec<u8>> {
+        if let Some(ranges) = &args.page_ranges {
+            check_page_ranges(ranges).map_err(Error::from)?;
+        }
+        let path = args.save_to.clone();
         let v = send_message!(self, "pdf", args);
         let b64 = only_str(&v)?;
         let bytes = base64::decode(b64).map_err(Error::InvalidBase64)?;
@@ -402,6 +434,7 @@ impl Page {
             .cloned()
             .collect();
         let f = get_object!(ctx, &guid, Frame)?;
+        upgrade(&f)?.on_detached();
         self.emit_event(Evt::FrameDetached(f));
         Ok(())
     }
@@ -462,6 +495,39 @@ impl Page {
         Ok(())
     }
 
+    #[cfg(feature = "log-forward")]
+    fn on_console(&self, console: &Weak<ConsoleMessage>) -> Result<(), Error> {
+        use crate::imp::log_forward;
+        let console = upgrade(console)?;
+        let url = &console.location().url;
+        let worker = self
+            .workers()
+            .iter()
+            .filter_map(Weak::upgrade)
+            .find(|w| !url.is_empty() && w.url() == url);
+        let target = match worker {
+            Some(w) => log_forward::worker_target(w.guid()),
+            None => log_forward::page_target(self.guid())
+        };
+        log_forward::console(&target, &console);
+        Ok(())
+    }
+
+    #[cfg(not(feature = "log-forward"))]
+    fn on_console(&self, _console: &Weak<ConsoleMessage>) -> Result<(), Error> { Ok(()) }
+
+    fn on_page_error(&self, params: Map<String, Value>) -> Result<(), Error> {
+        #[cfg(feature = "log-forward")]
+        {
+            use crate::imp::log_forward;
+            log_forward::page_error(&log_forward::page_target(self.guid()), &params);
+        }
+        #[cfg(not(feature = "log-forward"))]
+        let _ = params;
+        self.emit_event(Evt::PageError);
+        Ok(())
+    }
+
     fn on_download(&self, ctx: &Context, params: Map<String, Value>) -> Result<(), Error> {
         #[derive(Deserialize)]
         #[serde(rename_all = "camelCase")]
@@ -541,8 +607,10 @@ impl RemoteObject for Page {
                 let first = first_object(&params).ok_or(Error::InvalidParams)?;
                 let OnlyGuid { guid } = serde_json::from_value((*first).clone())?;
                 let console = get_object!(ctx, &guid, ConsoleMessage)?;
+                self.on_console(&console)?;
                 self.emit_event(Evt::Console(console));
             }
+            "pageError" => self.on_page_error(params)?,
             "request" => {
                 let first = first_object(&params).ok_or(Error::InvalidParams)?;
                 let OnlyGuid { guid } = serde_json::from_value((*first).clone())?;
@@ -678,6 +746,79 @@ struct Initializer {
     viewport: Option<Viewport>
 }
 
+/// Takes `[x, y, deltaX, deltaY]`. Scrolls the closest scrollable ancestor of the element under
+/// the point, or the window, unless the event is cancelled.
+const WHEEL: &str = r#"([x, y, deltaX, deltaY]) => {
+    const root = document.scrollingElement || document.documentElement;
+    const target = document.elementFromPoint(x, y) || root;
+    const event = new WheelEvent('wheel', {
+        bubbles: true, cancelable: true, composed: true,
+        clientX: x, clientY: y, deltaX, deltaY, deltaMode: WheelEvent.DOM_DELTA_PIXEL
+    });
+    if (!target.dispatchEvent(event))
+        return;
+    const scrollable = e => {
+        const s = getComputedStyle(e);
+        const y = /auto|scroll|overlay/.test(s.overflowY) && e.scrollHeight > e.clientHeight;
+        const x = /auto|scroll|overlay/.test(s.overflowX) && e.scrollWidth > e.clientWidth;
+        return (deltaY && y) || (deltaX && x);
+    };
+    for (let e = target; e && e !== root && e !== document.body; e = e.parentElement) {
+        if (scrollable(e)) {
+            e.scrollBy(deltaX, deltaY);
+            return;
+        }
+    }
+    window.scrollBy(deltaX, deltaY);
+}"#;
+
+#[skip_serializing_none]
+#[derive(Debug, Serialize, Default)]
+pub(crate) struct TouchGestureArgs {
+    pub(crate) fingers: Vec<TouchPath>,
+    pub(crate) duration: Option<f64>,
+    pub(crate) steps: Option<i32>
+}
+
+/// A finger moving in a straight line
+#[derive(Debug, Serialize)]
+pub(crate) struct TouchPath {
+    pub(crate) from: Position,
+    pub(crate) to: Position
+}
+
+/// Takes [`TouchGestureArgs`]. Each finger's events go to the element it first touched.
+const GESTURE: &str = r#"async ({ fingers, duration = 300, steps = 10 }) => {
+    const sleep = ms => new Promise(f => setTimeout(f, ms));
+    const targets = fingers.map(f => document.elementFromPoint(f.from.x, f.from.y) || document.body);
+    const at = (f, t) => [f.from.x + (f.to.x - f.from.x) * t, f.from.y + (f.to.y - f.from.y) * t];
+    const touch = (i, [x, y]) => new Touch({
+        identifier: i, target: targets[i], clientX: x, clientY: y,
+        pageX: x + window.scrollX, pageY: y + window.scrollY, screenX: x, screenY: y,
+        radiusX: 1, radiusY: 1, force: 1
+    });
+    const fire = (type, t) => {
+        const changedTouches = fingers.map((f, i) => touch(i, at(f, t)));
+        const touches = type === 'touchend' ? [] : changedTouches;
+        for (const target of new Set(targets)) {
+            target.dispatchEvent(new TouchEvent(type, {
+                bubbles: true, cancelable: true, composed: true,
+                touches,
+                targetTouches: touches.filter(x => x.target === target),
+                changedTouches: changedTouches.filter(x => x.target === target)
+            }));
+        }
+    };
+    const interval = duration / (steps + 1);
+    fire('touchstart', 0);
+    for (let i = 1; i <= steps; i++) {
+        await sleep(interval);
+        fire('touchmove', i / steps);
+    }
+    await sleep(interval);
+    fire('touchend', 1);
+}"#;
+
 #[skip_serializing_none]
 #[derive(Serialize)]
 #[serde(rename_all = "camelCase")]
@@ -763,21 +904,22 @@ pub(crate) struct ReloadArgs {
 #[skip_serializing_none]
 #[derive(Serialize, Default)]
 #[serde(rename_all = "camelCase")]
-pub(crate) struct PdfArgs<'a, 'b, 'c, 'd, 'e, 'f, 'g, 'h, 'i, 'j> {
+pub(crate) struct PdfArgs {
     pub(crate) scale: Option<f64>,
     pub(crate) display_header_footer: Option<bool>,
-    pub(crate) header_template: Option<&'a str>,
-    pub(crate) footer_template: Option<&'b str>,
+    pub(crate) header_template: Option<String>,
+    pub(crate) footer_template: Option<String>,
     pub(crate) print_background: Option<bool>,
     pub(crate) landscape: Option<bool>,
-    pub(crate) page_ranges: Option<&'c str>,
-    pub(crate) format: Option<&'d str>,
-    pub(crate) width: Option<Length<'e>>,
-    pub(crate) height: Option<Length<'f>>,
+    pub(crate) page_ranges: Option<String>,
+    pub(crate) format: Option<PaperFormat>,
+    pub(crate) width: Option<Length>,
+    pub(crate) height: Option<Length>,
     #[serde(rename = "preferCSSPageSize")]
     pub(crate) prefer_css_page_size: Option<bool>,
-    pub(crate) margin: Option<PdfMargins<'g, 'h, 'i, 'j>>,
-    pub(crate) path: Option<PathBuf>
+    pub(crate) margin: Option<Margin>,
+    #[serde(skip)]
+    pub(crate) save_to: Option<PathBuf>
 }
 
 #[skip_serializing_none]
@@ -2,11 +2,17 @@ use crate::imp::{core::*, prelude::*};
 
 #[derive(Debug)]
 pub(crate) struct Selectors {
-    channel: ChannelOwner
+    channel: ChannelOwner,
+    engines: Mutex<Vec<String>>
 }
 
 impl Selectors {
-    pub(crate) fn new(channel: ChannelOwner) -> Self { Self { channel } }
+    pub(crate) fn new(channel: ChannelOwner) -> Self {
+        Self {
+            channel,
+            engines: Mutex::default()
+        }
+    }
 
     pub(crate) async fn register(
         &self,
@@ -20,6 +26,16 @@ impl Selectors {
             content_script
         };
         let _ = send_message!(self, "register", args);
+        self.engines.lock().unwrap().push(name.into());
+        Ok(())
+    }
+
+    /// Names of the engines registered through this client
+    pub(crate) fn engines(&self) -> Vec<String> { self.engines.lock().unwrap().clone() }
+
+    pub(crate) fn set_test_id_attribute(&self, name: &str) -> Result<(), Error> {
+        let ctx = self.context()?;
+        ctx.lock().unwrap().set_test_id_attribute(name.into());
         Ok(())
     }
 }
@@ -140,6 +140,24 @@ pub enum MouseButton {
     Right
 }
 
+/// Where an element ends up when scrolled into view, along one axis
+#[derive(Debug, Deserialize, Serialize, Eq, PartialEq, Clone, Copy)]
+#[serde(rename_all = "lowercase")]
+pub enum ScrollAlignment {
+    Start,
+    Center,
+    End,
+    /// Scrolls as little as possible
+    Nearest
+}
+
+#[derive(Debug, Deserialize, Serialize, Eq, PartialEq, Clone, Copy)]
+#[serde(rename_all = "lowercase")]
+pub enum ScrollBehavior {
+    Auto,
+    Smooth
+}
+
 #[derive(Debug, Deserialize, Serialize, PartialEq, Clone, Copy)]
 pub struct Position {
     pub x: f64,
@@ -160,6 +178,15 @@ pub struct FloatRect {
     pub height: f64
 }
 
+impl FloatRect {
+    pub fn center(&self) -> Position {
+        Position {
+            x: self.x + self.width / 2.0,
+            y: self.y + self.height / 2.0
+        }
+    }
+}
+
 #[derive(Debug, Deserialize, Serialize, Eq, PartialEq, Clone, Copy)]
 #[serde(rename_all = "lowercase")]
 pub enum ScreenshotType {
@@ -167,6 +194,22 @@ pub enum ScreenshotType {
     Png
 }
 
+#[derive(Debug, Deserialize, Serialize, Eq, PartialEq, Clone, Copy)]
+#[serde(rename_all = "lowercase")]
+pub enum ScreenshotAnimations {
+    Allow,
+    /// Finite animations and transitions are fast-forwarded to their end, infinite ones are
+    /// cancelled to their initial state
+    Disabled
+}
+
+#[derive(Debug, Deserialize, Serialize, Eq, PartialEq, Clone, Copy)]
+#[serde(rename_all = "lowercase")]
+pub enum ScreenshotCaret {
+    Hide,
+    Initial
+}
+
 #[derive(Debug, Deserialize, Serialize, Eq, PartialEq, Clone, Copy)]
 #[serde(rename_all = "lowercase")]
 pub enum ElementState {
@@ -201,28 +244,162 @@ impl From<(String, String)> for Header {
     fn from((k, v): (String, String)) -> Self { Self { name: k, value: v } }
 }
 
-#[derive(Debug, Serialize, PartialEq, Clone)]
-#[serde(untagged)]
-pub enum Length<'a> {
-    Value(f64),
-    WithUnit(&'a str)
+/// Paper sizes for PDFs
+#[derive(Debug, Deserialize, Serialize, Eq, PartialEq, Clone, Copy)]
+pub enum PaperFormat {
+    /// 8.5in x 11in
+    Letter,
+    /// 8.5in x 14in
+    Legal,
+    /// 11in x 17in
+    Tabloid,
+    /// 17in x 11in
+    Ledger,
+    /// 33.1in x 46.8in
+    A0,
+    /// 23.4in x 33.1in
+    A1,
+    /// 16.54in x 23.4in
+    A2,
+    /// 11.7in x 16.54in
+    A3,
+    /// 8.27in x 11.7in
+    A4,
+    /// 5.83in x 8.27in
+    A5,
+    /// 4.13in x 5.83in
+    A6
+}
+
+/// A CSS length for PDF paper sizes and margins. Numbers without a unit are pixels.
+///
+/// ```
+/// # use playwright::api::Length;
+/// assert_eq!("2.5cm".parse(), Ok(Length::Cm(2.5)));
+/// assert_eq!(Length::from(100.0).to_string(), "100px");
+/// ```
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum Length {
+    Px(f64),
+    In(f64),
+    Cm(f64),
+    Mm(f64)
+}
+
+impl Default for Length {
+    fn default() -> Self { Self::Px(0.0) }
+}
+
+impl From<f64> for Length {
+    fn from(x: f64) -> Self { Self::Px(x) }
+}
+
+impl std::fmt::Display for Length {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Px(x) => write!(f, "{}px", x),
+            Self::In(x) => write!(f, "{}in", x),
+            Self::Cm(x) => write!(f, "{}cm", x),
+            Self::Mm(x) => write!(f, "{}mm", x)
+        }
+    }
 }
 
-impl<'a> From<f64> for Length<'a> {
-    fn from(x: f64) -> Self { Self::Value(x) }
+impl std::str::FromStr for Length {
+    type Err = PdfOptionError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let t = s.trim();
+        let units: [(&str, fn(f64) -> Length); 4] = [
+            ("px", Length::Px),
+            ("in", Length::In),
+            ("cm", Length::Cm),
+            ("mm", Length::Mm)
+        ];
+        let (number, unit) = units
+            .iter()
+            .find_map(|(u, f)| t.strip_suffix(u).map(|n| (n, *f)))
+            .unwrap_or((t, Length::Px));
+        match number.trim().parse::<f64>() {
+            Ok(x) if x.is_finite() => Ok(unit(x)),
+            _ => Err(PdfOptionError::Length(s.into()))
+        }
+    }
 }
 
-impl<'a> From<&'a str> for Length<'a> {
-    fn from(x: &'a str) -> Self { Self::WithUnit(x) }
+impl Serialize for Length {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer
+    {
+        serializer.collect_str(self)
+    }
 }
 
-#[skip_serializing_none]
-#[derive(Debug, Serialize, PartialEq, Clone)]
-pub struct PdfMargins<'a, 'b, 'c, 'd> {
-    pub top: Option<Length<'a>>,
-    pub right: Option<Length<'b>>,
-    pub bottom: Option<Length<'c>>,
-    pub left: Option<Length<'d>>
+/// Paper margins for PDFs
+///
+/// ```
+/// # use playwright::api::{Length, Margin};
+/// let margin = Margin {
+///     top: Length::Cm(2.0),
+///     ..Margin::all(Length::Mm(15.0))
+/// };
+/// ```
+#[derive(Debug, Serialize, PartialEq, Clone, Copy, Default)]
+pub struct Margin {
+    pub top: Length,
+    pub right: Length,
+    pub bottom: Length,
+    pub left: Length
+}
+
+impl Margin {
+    pub fn all(x: Length) -> Self {
+        Self {
+            top: x,
+            right: x,
+            bottom: x,
+            left: x
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, thiserror::Error)]
+pub enum PdfOptionError {
+    #[error("Invalid length {0:?}, expected a number with an optional px, in, cm or mm unit")]
+    Length(String),
+    #[error("Invalid page ranges {0:?}, expected 1-based pages and ranges like \"1-5, 8, 11-\"")]
+    PageRanges(String)
+}
+
+/// Checks comma separated pages and ranges such as `1-5, 8, 11-13`. Either end of a range may be
+/// left open. An empty string means all pages.
+pub(crate) fn check_page_ranges(ranges: &str) -> Result<(), PdfOptionError> {
+    if ranges.trim().is_empty() {
+        return Ok(());
+    }
+    let invalid = || PdfOptionError::PageRanges(ranges.into());
+    let page = |s: &str| s.trim().parse::<u32>().ok().filter(|&n| n >= 1);
+    for part in ranges.split(',').map(str::trim) {
+        match part.find('-') {
+            None => {
+                page(part).ok_or_else(invalid)?;
+            }
+            Some(i) => {
+                let (from, to) = (part[..i].trim(), part[i + 1..].trim());
+                if from.is_empty() && to.is_empty() {
+                    return Err(invalid());
+                }
+                let from = if from.is_empty() { Some(1) } else { page(from) };
+                let to = if to.is_empty() { Some(u32::MAX) } else { page(to) };
+                match (from, to) {
+                    (Some(from), Some(to)) if from <= to => {}
+                    _ => return Err(invalid())
+                }
+            }
+        }
+    }
+    Ok(())
 }
 
 #[derive(Debug, Serialize, PartialEq)]
@@ -284,3 +461,124 @@ pub struct ResponseTiming {
     /// Time immediately after the browser starts requesting the resource from the server, cache, or local resource. The value\nis given in milliseconds relative to `startTime`, -1 if not available.
     pub response_start: f64
 }
+
+/// Matches urls by glob or regular expression
+///
+/// In globs, `*` matches any characters except `/`, `**` matches any characters,
+/// `?` matches one character and `{a,b}` matches either alternative.
+/// `\` escapes the next character.
+/// Globs are compiled once, when the matcher is built.
+#[derive(Debug, Clone)]
+pub struct UrlMatcher(Matcher);
+
+#[derive(Debug, Clone)]
+enum Matcher {
+    Regex(regex::Regex),
+    /// A glob the regex engine rejects, e.g. for its size. It only matches itself.
+    Literal(String)
+}
+
+impl UrlMatcher {
+    pub fn is_match(&self, url: &str) -> bool {
+        match &self.0 {
+            Matcher::Regex(r) => r.is_match(url),
+            Matcher::Literal(s) => s == url
+        }
+    }
+}
+
+impl From<&str> for UrlMatcher {
+    fn from(glob: &str) -> Self {
+        Self(match glob_to_regex(glob) {
+            Ok(r) => Matcher::Regex(r),
+            Err(_) => Matcher::Literal(glob.into())
+        })
+    }
+}
+
+impl From<String> for UrlMatcher {
+    fn from(glob: String) -> Self { Self::from(&*glob) }
+}
+
+impl From<regex::Regex> for UrlMatcher {
+    fn from(r: regex::Regex) -> Self { Self(Matcher::Regex(r)) }
+}
+
+fn glob_to_regex(glob: &str) -> Result<regex::Regex, regex::Error> {
+    let mut re = String::from("^");
+    let mut in_group = false;
+    let mut chars = glob.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => {
+                if let Some(n) = chars.next() {
+                    re.push_str(&regex::escape(&n.to_string()));
+                }
+            }
+            '*' if chars.peek() == Some(&'*') => {
+                chars.next();
+                re.push_str(".*");
+            }
+            '*' => re.push_str("[^/]*"),
+            '?' => re.push('.'),
+            '{' if !in_group && closes_group(chars.clone()) => {
+                in_group = true;
+                re.push('(');
+            }
+            '}' if in_group => {
+                in_group = false;
+                re.push(')');
+            }
+            ',' if in_group => re.push('|'),
+            _ => re.push_str(&regex::escape(&c.to_string()))
+        }
+    }
+    re.push('$');
+    regex::Regex::new(&re)
+}
+
+/// Whether an unescaped `}` follows
+fn closes_group(mut chars: impl Iterator<Item = char>) -> bool {
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => {
+                chars.next();
+            }
+            '}' => return true,
+            _ => {}
+        }
+    }
+    false
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn glob() {
+        let m = |g: &str, u: &str| UrlMatcher::from(g).is_match(u);
+        assert!(m("**/*.js", "https://a.com/b/c.js"));
+        assert!(!m("https://a.com/*.js", "https://a.com/b/c.js"));
+        assert!(m("https://a.com/?.{png,jpg}", "https://a.com/x.jpg"));
+        assert!(m("https://a.com/a+b", "https://a.com/a+b"));
+        assert!(!m("https://a.com/", "https://a.com/index.html"));
+        assert!(m("{a\\}", "{a}"));
+        assert!(m("{a,b\\}}", "b}"));
+    }
+
+    #[test]
+    fn pdf_options() {
+        assert_eq!("10".parse(), Ok(Length::Px(10.0)));
+        assert_eq!(" 1.5 in".parse(), Ok(Length::In(1.5)));
+        assert!("10pt".parse::<Length>().is_err());
+        assert_eq!(serde_json::to_value(Length::Mm(3.0)).unwrap(), "3mm");
+        assert!(check_page_ranges("").is_ok());
+        assert!(check_page_ranges("1-5, 8, 11-13").is_ok());
+        assert!(check_page_ranges("-3,5-").is_ok());
+        assert!(check_page_ranges("5-3").is_err());
+        assert!(check_page_ranges("0").is_err());
+        assert!(check_page_ranges("1,,2").is_err());
+        assert!(check_page_ranges("a-b").is_err());
+    }
+}
@@ -7,7 +7,7 @@ pub mod api;
 mod imp;
 
 pub use crate::imp::core::{Driver, Error};
-pub use api::playwright::Playwright;
+pub use api::{expect, playwright::Playwright};
 
 #[doc(hidden)]
 #[macro_export]
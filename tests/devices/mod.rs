@@ -21,6 +21,7 @@ pub async fn all(playwright: &Playwright, port: u16, _which: Which) {
     check_size(&page).await;
     assert!((device_pixel_ratio(&page).await - 3.0).abs() < f64::EPSILON);
     assert!(has_touch(&page).await);
+    check_gestures(&page).await;
     // TODO: is_mobile
     let tmp_dir = tempdir::TempDir::new("playwright-rust").unwrap();
     dbg!(&tmp_dir);
@@ -71,6 +72,44 @@ async fn has_touch(page: &Page) -> bool {
     page.eval("() => 'ontouchstart' in window").await.unwrap()
 }
 
+async fn check_gestures(page: &Page) {
+    page.set_content_builder(
+        r#"<div id="area" style="width: 300px; height: 300px"></div>
+        <script>
+        window.log = [];
+        for (const t of ['touchstart', 'touchmove', 'touchend'])
+            area.addEventListener(t, e => log.push([t, e.touches.length, e.changedTouches.length]));
+        </script>"#
+    )
+    .set_content()
+    .await
+    .unwrap();
+    let center = page
+        .query_selector("#area")
+        .await
+        .unwrap()
+        .unwrap()
+        .bounding_box()
+        .await
+        .unwrap()
+        .unwrap()
+        .center();
+    page.touch_screen
+        .pinch_builder(center, 40.0, 200.0)
+        .steps(3)
+        .gesture()
+        .await
+        .unwrap();
+    let log: Vec<(String, i32, i32)> = page.eval("() => log").await.unwrap();
+    let types: Vec<&str> = log.iter().map(|(t, _, _)| t.as_str()).collect();
+    assert_eq!(
+        types,
+        ["touchstart", "touchmove", "touchmove", "touchmove", "touchend"]
+    );
+    assert_eq!((log[0].1, log[0].2), (2, 2));
+    assert_eq!((log[4].1, log[4].2), (0, 2));
+}
+
 async fn check_user_agent(page: &Page, port: u16) {
     let user_agent = "Mozilla/5.0 (iPhone; CPU iPhone OS 12_2 like Mac OS X) AppleWebKit/605.1.15 (KHTML, like Gecko) Version/13.0 Mobile/15E148 Safari/604.1";
     assert_eq!(
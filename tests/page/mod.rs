@@ -37,7 +37,23 @@ pub async fn all(c: &BrowserContext, port: u16, which: Which) {
         workers_should_work(c, port, which),
         accessibility(c),
         query_selector_and_eval(c),
-        input(c)
+        input(c),
+        locator(c),
+        assertions(c),
+        get_by(c),
+        generate_selector(c),
+        frame_locator(c),
+        frames(c),
+        extract(c),
+        fill_form(c),
+        extract_table(c),
+        js_value(c),
+        js_types(c),
+        js_handle(c),
+        scroll(c),
+        drag_and_drop(c),
+        keys(c),
+        screenshot_options(c)
     );
     // TODO
     // file_chooser(c, port).await;
@@ -384,10 +400,72 @@ async fn screenshot_should_work(p: &Page) {
     assert!(path.is_file());
 }
 
+async fn screenshot_options(c: &BrowserContext) {
+    use playwright::api::{ScreenshotAnimations, ScreenshotCaret};
+    let p = new(c).await;
+    done!(p
+        .set_content_builder(
+            r#"<style>
+            @keyframes fade { to { opacity: 0 } }
+            @keyframes spin { to { transform: rotate(360deg) } }
+            </style>
+            <div id="fade" style="animation: fade 10s">fade</div>
+            <div id="spin" style="animation: spin 1s infinite">spin</div>
+            <input id="name" autofocus>
+            <iframe srcdoc="<p>inner</p>"></iframe>"#
+        )
+        .set_content());
+    let spin = done!(p.query_selector("#spin")).unwrap();
+    let bytes = done!(p
+        .screenshot_builder()
+        .add_mask("#fade")
+        .add_mask(&spin)
+        .mask_color("#00FF00")
+        .animations(ScreenshotAnimations::Disabled)
+        .caret(ScreenshotCaret::Hide)
+        .screenshot());
+    let image = image::load_from_memory(&bytes).unwrap().to_rgba8();
+    let fade = done!(done!(p.query_selector("#fade")).unwrap().bounding_box()).unwrap();
+    let scale: f64 = done!(p.eval("() => devicePixelRatio"));
+    let (x, y) = (fade.x + fade.width / 2., fade.y + fade.height / 2.);
+    let pixel = image.get_pixel((x * scale) as u32, (y * scale) as u32);
+    assert_eq!(pixel.0, [0, 255, 0, 255]);
+    let (overlays, animations): (i32, i32) = done!(p.eval(
+        "() => [document.querySelectorAll('.__playwright_capture__').length, \
+        document.getAnimations().length]"
+    ));
+    assert_eq!((overlays, animations), (0, 0));
+    let name = done!(p.query_selector("#name")).unwrap();
+    let bytes = done!(name
+        .screenshot_builder()
+        .await
+        .add_mask(&name)
+        .caret(ScreenshotCaret::Hide)
+        .screenshot());
+    assert!(!bytes.is_empty());
+    let inner = done!(p.frames().unwrap()[1].query_selector("p")).unwrap();
+    let err = p.screenshot_builder().add_mask(&inner).screenshot().await.unwrap_err();
+    assert!(matches!(*err, playwright::Error::MaskAcrossFrames));
+    close(&p).await;
+}
+
 async fn pdf_should_work(p: &Page) {
+    use playwright::api::{Length, Margin, PaperFormat};
     let path = super::temp_dir().join("pdf.pdf");
-    p.pdf_builder().path(path.clone()).pdf().await.unwrap();
-    assert!(path.is_file());
+    let pdf = p
+        .pdf_builder()
+        .format(PaperFormat::A4)
+        .margin(Margin::all(Length::Cm(1.0)))
+        .footer_template(r#"<span class="pageNumber"></span>"#)
+        .page_ranges("1-")
+        .save_to(path.clone())
+        .pdf()
+        .await
+        .unwrap();
+    assert!(pdf.starts_with(b"%PDF"));
+    assert_eq!(std::fs::read(&path).unwrap(), pdf);
+    let err = p.pdf_builder().page_ranges("3-1").pdf().await.unwrap_err();
+    assert!(matches!(*err, playwright::Error::PdfOption(_)));
 }
 
 async fn emulate_media(p: &Page) {
@@ -568,6 +646,387 @@ async fn query_selector_and_eval(c: &BrowserContext) {
     close(&p).await;
 }
 
+async fn locator(c: &BrowserContext) {
+    let p = new(c).await;
+    done!(p
+        .set_content_builder(
+            r#"<ul><li>a</li><li>b</li><li>c</li></ul><input type="text" /><button onclick="this.innerText = 'clicked'">button</button>"#
+        )
+        .set_content());
+    let items = p.locator("ul").locator("li");
+    assert_eq!(done!(items.count()), 3);
+    assert_eq!(done!(items.first().text_content()).as_deref(), Some("a"));
+    assert_eq!(done!(items.last().text_content()).as_deref(), Some("c"));
+    assert_eq!(done!(items.nth(1).inner_text()), "b");
+    assert_eq!(done!(items.all()).len(), 3);
+    match items.text_content().await {
+        Err(e) => assert!(matches!(*e, playwright::Error::StrictModeViolation(_, 3))),
+        Ok(_) => panic!("Not expected")
+    }
+    done!(p.locator("input").fill_builder("foo").fill());
+    assert_eq!(
+        done!(p.eval::<String>("() => document.querySelector('input').value")),
+        "foo"
+    );
+    let button = p.locator("button");
+    done!(button.click_builder().click());
+    assert_eq!(done!(button.inner_text()), "clicked");
+    let ul = done!(p.query_selector("ul")).unwrap();
+    assert_eq!(done!(ul.locator("li").count()), 3);
+    // The disabled button is resolved, then replaced while the click waits for it to be enabled
+    done!(p.eval::<()>(
+        r#"() => {
+            document.body.innerHTML = '<button id="r" disabled>old</button>';
+            setTimeout(() => {
+                const b = document.createElement('button');
+                b.id = 'r';
+                b.onclick = () => b.innerText = 'clicked';
+                document.querySelector('#r').replaceWith(b);
+            }, 300);
+        }"#
+    ));
+    let rerendered = p.locator("#r");
+    done!(rerendered.click_builder().timeout(5000.).click());
+    assert_eq!(done!(rerendered.inner_text()), "clicked");
+    close(&p).await;
+}
+
+async fn assertions(c: &BrowserContext) {
+    use playwright::expect::expect;
+    let p = new(c).await;
+    done!(p
+        .set_content_builder(
+            r#"<title>t</title><h1>  Hello
+            world </h1><p hidden>p</p><button onclick="setTimeout(() => this.innerText = 'done', 300)">go</button>"#
+        )
+        .set_content());
+    done!(expect(&p.locator("h1")).to_have_text("Hello world"));
+    done!(expect(&p.locator("h1")).to_contain_text("world"));
+    done!(expect(&p.locator("p")).not().to_be_visible());
+    done!(expect(&p).to_have_title("t"));
+    let button = p.locator("button");
+    done!(button.click_builder().click());
+    done!(expect(&button).to_have_text("done"));
+    match expect(&p.locator("h1")).timeout(300.).to_have_text("Bye").await {
+        Err(e) => assert!(matches!(*e, playwright::Error::Assertion(_))),
+        Ok(_) => panic!("Not expected")
+    }
+    close(&p).await;
+}
+
+async fn get_by(c: &BrowserContext) {
+    use playwright::api::AriaRole;
+    let p = new(c).await;
+    done!(p
+        .set_content_builder(
+            r#"<label for="email">Email</label><input id="email" /><input placeholder="Search" /><button data-testid="go">Go</button><input type="checkbox" aria-label="Agree" checked />"#
+        )
+        .set_content());
+    let go = p.get_by_role(AriaRole::Button).name("Go").selector();
+    done!(p.click_builder(&go).click());
+    assert_eq!(done!(p.get_by_test_id("go").locator().inner_text()), "Go");
+    assert_eq!(done!(p.get_by_text("go").locator().count()), 1);
+    done!(p.get_by_label("mail").locator().fill_builder("a@example.com").fill());
+    assert_eq!(
+        done!(p.eval::<String>("() => document.querySelector('#email').value")),
+        "a@example.com"
+    );
+    assert_eq!(done!(p.get_by_placeholder("Sea").locator().count()), 1);
+    assert_eq!(done!(p.get_by_placeholder("Sea").exact(true).locator().count()), 0);
+    let agree = p.get_by_role(AriaRole::Checkbox).name("Agree").checked(true);
+    assert_eq!(done!(agree.locator().count()), 1);
+    close(&p).await;
+}
+
+async fn generate_selector(c: &BrowserContext) {
+    let p = new(c).await;
+    done!(p
+        .set_content_builder(
+            r#"<div><button data-testid="save">Save</button><button>Save</button></div><ul><li>a</li><li>b</li></ul>"#
+        )
+        .set_content());
+    let save = done!(p.query_selector("button")).unwrap();
+    assert_eq!(
+        done!(save.generate_selector()).as_deref(),
+        Some(r#"[data-testid="save"]"#)
+    );
+    let b = done!(p.query_selector("li:nth-child(2)")).unwrap();
+    let selectors = done!(b.generate_selectors());
+    assert!(!selectors.is_empty());
+    for s in &selectors {
+        assert_eq!(done!(p.query_selector_all(s)).len(), 1);
+    }
+    assert_eq!(done!(b.generate_selector_chain()).map(|c| c.len()), Some(1));
+    close(&p).await;
+}
+
+async fn frame_locator(c: &BrowserContext) {
+    let p = new(c).await;
+    done!(p
+        .set_content_builder(
+            r#"<iframe id="outer" srcdoc="<iframe srcdoc='<button onclick=&quot;this.innerText = 1&quot;>inner</button>'></iframe><p>outer</p>"></iframe>"#
+        )
+        .set_content());
+    let outer = p.frame_locator("#outer");
+    assert_eq!(done!(outer.locator("p").inner_text()), "outer");
+    let button = outer.frame_locator("iframe").locator("button");
+    done!(button.click_builder().click());
+    assert_eq!(done!(button.inner_text()), "1");
+    assert_eq!(done!(p.frame_locator("iframe").locator("p").count()), 1);
+    done!(p.eval::<()>("() => { const f = document.querySelector('#outer'); f.srcdoc = '<p>reloaded</p>'; }"));
+    done!(playwright::expect::expect(&outer.locator("p")).to_have_text("reloaded"));
+    let err = p.frame_locator("#outer >> p").locator("a").count().await.unwrap_err();
+    assert!(matches!(*err, playwright::Error::NotIframe(_)));
+    close(&p).await;
+}
+
+async fn frames(c: &BrowserContext) {
+    use playwright::api::page::FrameMatcher;
+    let p = new(c).await;
+    done!(p
+        .set_content_builder(r#"<iframe name="a" srcdoc="<p>a</p>"></iframe>"#)
+        .set_content());
+    let a = p.frame_by_name("a").unwrap().unwrap();
+    let by_url = p.frame_by_url("about:srcdoc").unwrap().unwrap();
+    assert_eq!(by_url.name().unwrap(), "a");
+    assert!(p.frame_by_name("b").unwrap().is_none());
+    let (b, _) = tokio::join!(
+        p.wait_for_frame(FrameMatcher::Name("b".into())),
+        p.eval::<()>("() => { const f = document.createElement('iframe'); f.name = 'b'; document.body.appendChild(f); }")
+    );
+    let b = b.unwrap();
+    let tree = p.frame_tree().unwrap();
+    assert_eq!(tree.children.len(), 2);
+    assert!(tree.to_string().contains("(a)"));
+    done!(p.eval::<()>("() => document.querySelector('iframe').remove()"));
+    while !a.is_detached() {
+        p.wait_for_timeout(50.).await;
+    }
+    assert!(!b.is_detached());
+    assert_eq!(p.frame_tree().unwrap().children.len(), 1);
+    close(&p).await;
+}
+
+async fn extract(c: &BrowserContext) {
+    use playwright::api::extract::{Extract, Field};
+    #[derive(Debug, serde::Deserialize, PartialEq)]
+    struct Link {
+        href: String,
+        label: String
+    }
+    #[derive(Debug, serde::Deserialize)]
+    struct Doc {
+        title: String,
+        missing: Option<String>,
+        links: Vec<Link>
+    }
+    impl Extract for Doc {
+        fn schema() -> Field {
+            let link = Field::object()
+                .field("href", Field::attribute("", "href"))
+                .field("label", Field::text(""));
+            Field::object()
+                .field("title", Field::text("h1"))
+                .field("missing", Field::text("h2"))
+                .field("links", Field::list("a", link))
+        }
+    }
+    let p = new(c).await;
+    done!(p
+        .set_content_builder(r#"<h1> Title </h1><a href="/a">A</a><a href="/b">B</a>"#)
+        .set_content());
+    let doc: Doc = done!(p.extract());
+    assert_eq!(doc.title, "Title");
+    assert_eq!(doc.missing, None);
+    assert_eq!(
+        doc.links,
+        vec![
+            Link {
+                href: "/a".into(),
+                label: "A".into()
+            },
+            Link {
+                href: "/b".into(),
+                label: "B".into()
+            }
+        ]
+    );
+    let hrefs = Field::list("a", Field::attribute("", "href"));
+    let hrefs: Vec<String> = done!(p.extract_with(&hrefs));
+    assert_eq!(hrefs, ["/a", "/b"]);
+    let own = Field::list("a", Field::list("", Field::text("")));
+    let own: Vec<Vec<String>> = done!(p.extract_with(&own));
+    assert_eq!(own, [["A"], ["B"]]);
+    close(&p).await;
+}
+
+async fn fill_form(c: &BrowserContext) {
+    let p = new(c).await;
+    done!(p
+        .set_content_builder(
+            r#"<form>
+                <input name="user">
+                <label>Bio <textarea id="bio"></textarea></label>
+                <select name="color">
+                    <option value="r">Red</option><option value="b">Blue</option>
+                </select>
+                <input type="checkbox" name="agree">
+                <input type="checkbox" name="tags" value="a">
+                <input type="checkbox" name="tags" value="b" checked>
+                <input type="radio" name="size" value="s"><input type="radio" name="size" value="l">
+                <input type="range" name="level">
+                <button>Send</button>
+            </form>"#
+        )
+        .set_content());
+    let value = serde_json::json!({
+        "user": "alice",
+        "Bio": "hello",
+        "color": "Blue",
+        "agree": true,
+        "tags": ["a"],
+        "size": "xl",
+        "level": 5,
+        "missing": 1
+    });
+    let report = done!(p.fill_form("form", &value));
+    assert_eq!(report.filled, ["Bio", "agree", "color", "tags", "user"]);
+    assert_eq!(report.unmatched, ["level", "missing", "size"]);
+    let state = done!(p.read_form("form"));
+    assert_eq!(
+        state,
+        serde_json::json!({
+            "user": "alice",
+            "bio": "hello",
+            "color": "b",
+            "agree": true,
+            "tags": ["a"],
+            "size": null,
+            "level": "50"
+        })
+    );
+    close(&p).await;
+}
+
+async fn extract_table(c: &BrowserContext) {
+    #[derive(Debug, serde::Deserialize, PartialEq)]
+    struct Row {
+        #[serde(rename = "Name")]
+        name: String,
+        #[serde(rename = "Score Q1")]
+        q1: String
+    }
+    let p = new(c).await;
+    done!(p
+        .set_content_builder(
+            r#"<table id="t">
+                <thead>
+                    <tr><th rowspan="2">Name</th><th colspan="2">Score</th></tr>
+                    <tr><th>Q1</th><th>Q2</th></tr>
+                </thead>
+                <tbody>
+                    <tr><td>a</td><td>1</td><td rowspan="2">2</td></tr>
+                    <tr><td>b</td><td>3</td></tr>
+                </tbody>
+            </table>
+            <div role="grid" id="g">
+                <div role="row"><span role="columnheader">K</span></div>
+                <div role="row"><span role="gridcell">x</span></div>
+            </div>"#
+        )
+        .set_content());
+    let rows = done!(p.extract_table("#t"));
+    assert_eq!(rows.len(), 2);
+    assert_eq!(rows[1]["Name"], "b");
+    assert_eq!(rows[1]["Score Q2"], "2");
+    let rows: Vec<Row> = done!(p.extract_table_builder("#t").deserialize());
+    assert_eq!(
+        rows[0],
+        Row {
+            name: "a".into(),
+            q1: "1".into()
+        }
+    );
+    let grid = done!(p.query_selector("#g")).unwrap();
+    let rows = done!(grid.table());
+    assert_eq!(rows[0]["K"], "x");
+    done!(p.eval::<i32>(
+        r#"() => {
+            const body = document.querySelector('#t tbody');
+            setTimeout(() => body.insertAdjacentHTML('beforeend', '<tr><td>c</td></tr>'), 200);
+            return 0;
+        }"#
+    ));
+    let table = done!(p.extract_table_builder("#t").stable(500.0).extract());
+    assert_eq!(table.rows.len(), 3);
+    close(&p).await;
+}
+
+async fn js_value(c: &BrowserContext) {
+    use playwright::api::JsValue;
+    let p = new(c).await;
+    let v: JsValue = done!(p.eval(
+        "() => ({ u: undefined, n: null, nan: NaN, z: -0, inf: -Infinity, \
+         d: new Date(0), a: [1, 'x'] })"
+    ));
+    assert_eq!(v.get("u"), Some(&JsValue::Undefined));
+    assert_eq!(v.get("n"), Some(&JsValue::Null));
+    assert_eq!(v.get("z"), Some(&JsValue::Number(-0.0)));
+    assert!(matches!(v.get("d"), Some(JsValue::Date(_))));
+    assert_eq!(
+        v.to_string(),
+        "{ u: undefined, n: null, nan: NaN, z: -0, inf: -Infinity, d: 1970-01-01T00:00:00.000Z, \
+         a: [1, 'x'] }"
+    );
+    let back: JsValue = done!(p.evaluate("x => x", v.clone()));
+    assert_eq!(back, v);
+    let t: String = done!(p.evaluate("x => typeof x.u + (1 / x.z)", v));
+    assert_eq!(t, "undefined-Infinity");
+    close(&p).await;
+}
+
+async fn js_types(c: &BrowserContext) {
+    use playwright::api::{Bytes, JsMap, JsSet};
+    let p = new(c).await;
+    let s: String = done!(p.evaluate(
+        "([b, m, s, n]) => [new Uint8Array(b)[1], new Map(m).get(2), new Set(s).size, n].join()",
+        (
+            Bytes(vec![0, 255]),
+            JsMap(vec![(2, "b")]),
+            JsSet(vec![1, 1]),
+            u64::MAX as u128 >> 12
+        )
+    ));
+    assert_eq!(s, "255,b,1,4503599627370495");
+    let b: Bytes = done!(p.eval("() => [...new Uint8Array([1, 2])]"));
+    assert_eq!(b, Bytes(vec![1, 2]));
+    let m: JsMap<i32, String> = done!(p.eval("() => [...new Map([[1, 'a']])]"));
+    assert_eq!(m, JsMap(vec![(1, "a".to_owned())]));
+    close(&p).await;
+}
+
+async fn js_handle(c: &BrowserContext) {
+    let p = new(c).await;
+    done!(p.set_content_builder(r#"<div id="a">x</div>"#).set_content());
+    let h = done!(p.evaluate_js_handle(
+        "() => ({ el: document.querySelector('#a'), n: 2 })",
+        None::<()>
+    ));
+    assert_eq!(h.to_string(), "JSHandle@object");
+    let n: i32 = done!(h.evaluate("(o, k) => o[k]", Some("n")));
+    assert_eq!(n, 2);
+    let el = done!(h.get_property("el"));
+    assert!(!el.to_string().is_empty());
+    let e = el.as_element().unwrap();
+    assert_eq!(done!(e.inner_text()), "x");
+    assert!(done!(h.get_property("n")).as_element().is_none());
+    let s: String = done!(p.evaluate("([o, e]) => o.n + e.id", (&h, &e)));
+    assert_eq!(s, "2a");
+    let doubled = done!(h.evaluate_handle("o => o.n * 2", None::<()>));
+    assert_eq!(done!(doubled.json_value::<i32>()), 4);
+    close(&p).await;
+}
+
 // async fn file_chooser(c: &BrowserContext, port: u16) {
 //    let p = new(c).await;
 //    let url = super::url_static(port, "/form.html");
@@ -603,3 +1062,93 @@ async fn query_selector_and_eval(c: &BrowserContext) {
 //    tokio::time::sleep(std::time::Duration::from_secs(5)).await;
 //    close(&p).await;
 //}
+
+async fn scroll(c: &BrowserContext) {
+    use playwright::api::ScrollAlignment;
+    let p = new(c).await;
+    done!(p
+        .set_content_builder(
+            r#"<div id="feed" style="height: 100px; overflow: auto">
+            <div style="height: 1000px"></div><p id="end">end</p></div>
+            <div style="height: 3000px"></div>
+            <script>window.wheels = 0; feed.addEventListener('wheel', () => wheels++);</script>"#
+        )
+        .set_content());
+    done!(p.mouse.move_builder(50.0, 50.0).steps(5).curve(0.3).r#move());
+    done!(p.mouse.wheel(0.0, 200.0));
+    let (wheels, top): (i32, f64) = done!(p.eval("() => [wheels, feed.scrollTop]"));
+    assert_eq!((wheels, top), (1, 200.0));
+    done!(p.scroll_by(0.0, 300.0));
+    let y: f64 = done!(p.eval("() => window.scrollY"));
+    assert_eq!(y, 300.0);
+    let end = done!(p.query_selector("#end")).unwrap();
+    done!(end
+        .scroll_into_view_builder()
+        .block(ScrollAlignment::End)
+        .scroll_into_view());
+    let bottom: bool =
+        done!(p.eval("() => feed.scrollTop + feed.clientHeight === feed.scrollHeight"));
+    assert!(bottom);
+    close(&p).await;
+}
+
+async fn drag_and_drop(c: &BrowserContext) {
+    let p = new(c).await;
+    done!(p
+        .set_content_builder(
+            r#"<div id="card" draggable="true">card</div>
+            <div id="done" style="height: 50px">done</div>
+            <div id="knob" style="width: 20px; height: 20px">knob</div>
+            <div id="zone" style="height: 50px">zone</div>
+            <div id="hidden" style="display: none">hidden</div>
+            <b id="late" hidden>late</b>
+            <iframe srcdoc="<p>inner</p>"></iframe>
+            <script>
+            card.addEventListener('dragstart', e => e.dataTransfer.setData('text/plain', 'card'));
+            done.addEventListener('dragover', e => e.preventDefault());
+            done.addEventListener('drop', e => done.dataset.got = e.dataTransfer.getData('text/plain'));
+            knob.addEventListener('mousedown', () => window.held = true);
+            zone.addEventListener('mouseup', () => zone.dataset.got = window.held);
+            zone.addEventListener('dragover', e => e.preventDefault());
+            zone.addEventListener('drop', e => zone.dataset.file = e.dataTransfer.files[0].name);
+            </script>"#
+        )
+        .set_content());
+    done!(p.drag_and_drop_builder("#card", "#done").drag_and_drop());
+    let got: Option<String> = done!(p.eval("() => done.dataset.got"));
+    assert_eq!(got.as_deref(), Some("card"));
+    let knob = done!(p.query_selector("#knob")).unwrap();
+    let zone = done!(p.query_selector("#zone")).unwrap();
+    done!(knob.drag_to(&zone));
+    assert_eq!(done!(zone.get_attribute("data-got")).as_deref(), Some("true"));
+    let hidden = done!(p.query_selector("#hidden")).unwrap();
+    let to_hidden = knob.drag_to_builder(&hidden).timeout(100.);
+    assert!(to_hidden.drag_and_drop().await.is_err());
+    // Waiting for the late source uses up most of the timeout shared with the hidden target
+    done!(p.eval::<()>("() => setTimeout(() => late.hidden = false, 300)"));
+    let started = std::time::Instant::now();
+    let late = p.drag_and_drop_builder("#late", "#hidden").timeout(600.);
+    assert!(late.drag_and_drop().await.is_err());
+    assert!(started.elapsed() < std::time::Duration::from_millis(850));
+    let inner = done!(p.frames().unwrap()[1].query_selector("p")).unwrap();
+    let err = knob.drag_to(&inner).await.unwrap_err();
+    assert!(matches!(*err, playwright::Error::DragAcrossFrames));
+    let file = File::new("a.txt".into(), "text/plain".into(), b"a");
+    assert!(done!(zone.drop_files(&[file])));
+    assert_eq!(done!(zone.get_attribute("data-file")).as_deref(), Some("a.txt"));
+    close(&p).await;
+}
+
+async fn keys(c: &BrowserContext) {
+    use playwright::api::{Key, Shortcut};
+    let p = new(c).await;
+    done!(p.set_content_builder(r#"<input id="i" value="ab">"#).set_content());
+    let i = done!(p.query_selector("#i")).unwrap();
+    done!(i.focus());
+    done!(i.press_builder(Key::End).press());
+    done!(i.press_builder(Key::Backspace).press());
+    done!(p.keyboard.press(Shortcut::char('C').with(Key::Shift), None));
+    done!(p.keyboard.press("Shift+KeyD", None));
+    assert_eq!(done!(p.eval::<String>("() => i.value")), "aCD");
+    close(&p).await;
+}